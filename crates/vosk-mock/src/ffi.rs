@@ -0,0 +1,528 @@
+//! The fake `vosk_*` functions, with the signatures declared by vosk-sys.
+
+use crate::{
+    state::{self, Kind},
+    Call,
+};
+use std::{
+    ffi::{CStr, CString},
+    os::raw::{c_char, c_int, c_short},
+    ptr, slice,
+};
+use vosk_sys::{
+    VoskBatchModel, VoskBatchRecognizer, VoskEndpointerMode, VoskModel, VoskRecognizer,
+    VoskSpkModel, VoskTextProcessor,
+};
+
+/// Reads a string argument, recording a misuse if it is NULL.
+unsafe fn read_string(ptr: *const c_char, function: &str) -> Option<String> {
+    if ptr.is_null() {
+        state::record_misuse(format!("{function}: NULL string"));
+        None
+    } else {
+        Some(CStr::from_ptr(ptr).to_string_lossy().into_owned())
+    }
+}
+
+/// Reads an audio buffer argument, recording a misuse if it is invalid.
+unsafe fn read_buffer<T: Copy>(ptr: *const T, length: c_int, function: &str) -> Vec<T> {
+    match usize::try_from(length) {
+        Ok(0) => Vec::new(),
+        Ok(length) if !ptr.is_null() => slice::from_raw_parts(ptr, length).to_vec(),
+        _ => {
+            state::record_misuse(format!(
+                "{function}: invalid buffer ({ptr:?}, length {length})"
+            ));
+            Vec::new()
+        }
+    }
+}
+
+/// Stores `result` in the object at `ptr` and returns a pointer to it.
+fn store_result<T>(ptr: *mut T, kind: Kind, function: &str, result: CString) -> *const c_char {
+    state::with_object(ptr, kind, function, |object| {
+        object.result = result;
+        object.result.as_ptr()
+    })
+    .unwrap_or(ptr::null())
+}
+
+fn set_flag(ptr: *mut VoskRecognizer, function: &str, call: Call) {
+    if state::with_object(ptr, Kind::Recognizer, function, |_| ()).is_some() {
+        state::record(call);
+    }
+}
+
+#[no_mangle]
+unsafe extern "C" fn vosk_model_new(model_path: *const c_char) -> *mut VoskModel {
+    let Some(path) = read_string(model_path, "vosk_model_new") else {
+        return ptr::null_mut();
+    };
+
+    state::record(Call::ModelNew { path });
+    state::create(Kind::Model)
+}
+
+#[no_mangle]
+unsafe extern "C" fn vosk_model_free(model: *mut VoskModel) {
+    state::record(Call::ModelFree);
+    state::free(model, Kind::Model, "vosk_model_free");
+}
+
+#[no_mangle]
+unsafe extern "C" fn vosk_model_find_word(model: *mut VoskModel, word: *const c_char) -> c_int {
+    let function = "vosk_model_find_word";
+    let Some(word) = read_string(word, function) else {
+        return -1;
+    };
+
+    state::record(Call::FindWord { word: word.clone() });
+    state::with_object(model, Kind::Model, function, |_| ())
+        .and_then(|()| state::find_word(&word))
+        .and_then(|symbol| c_int::try_from(symbol).ok())
+        .unwrap_or(-1)
+}
+
+#[no_mangle]
+unsafe extern "C" fn vosk_spk_model_new(model_path: *const c_char) -> *mut VoskSpkModel {
+    let Some(path) = read_string(model_path, "vosk_spk_model_new") else {
+        return ptr::null_mut();
+    };
+
+    state::record(Call::SpeakerModelNew { path });
+    state::create(Kind::SpeakerModel)
+}
+
+#[no_mangle]
+unsafe extern "C" fn vosk_spk_model_free(model: *mut VoskSpkModel) {
+    state::record(Call::SpeakerModelFree);
+    state::free(model, Kind::SpeakerModel, "vosk_spk_model_free");
+}
+
+#[no_mangle]
+unsafe extern "C" fn vosk_recognizer_new(
+    model: *mut VoskModel,
+    sample_rate: f32,
+) -> *mut VoskRecognizer {
+    state::record(Call::RecognizerNew { sample_rate });
+
+    match state::with_object(model, Kind::Model, "vosk_recognizer_new", |_| ()) {
+        Some(()) => state::create(Kind::Recognizer),
+        None => ptr::null_mut(),
+    }
+}
+
+#[no_mangle]
+unsafe extern "C" fn vosk_recognizer_new_spk(
+    model: *mut VoskModel,
+    sample_rate: f32,
+    spk_model: *mut VoskSpkModel,
+) -> *mut VoskRecognizer {
+    let function = "vosk_recognizer_new_spk";
+    state::record(Call::RecognizerNewSpeaker { sample_rate });
+
+    let valid = state::with_object(model, Kind::Model, function, |_| ()).is_some()
+        && state::with_object(spk_model, Kind::SpeakerModel, function, |_| ()).is_some();
+
+    if valid {
+        state::create(Kind::Recognizer)
+    } else {
+        ptr::null_mut()
+    }
+}
+
+#[no_mangle]
+unsafe extern "C" fn vosk_recognizer_new_grm(
+    model: *mut VoskModel,
+    sample_rate: f32,
+    grammar: *const c_char,
+) -> *mut VoskRecognizer {
+    let function = "vosk_recognizer_new_grm";
+    let Some(grammar) = read_string(grammar, function) else {
+        return ptr::null_mut();
+    };
+
+    state::record(Call::RecognizerNewGrammar {
+        sample_rate,
+        grammar,
+    });
+
+    match state::with_object(model, Kind::Model, function, |_| ()) {
+        Some(()) => state::create(Kind::Recognizer),
+        None => ptr::null_mut(),
+    }
+}
+
+#[no_mangle]
+unsafe extern "C" fn vosk_recognizer_set_spk_model(
+    recognizer: *mut VoskRecognizer,
+    spk_model: *mut VoskSpkModel,
+) {
+    let function = "vosk_recognizer_set_spk_model";
+
+    if state::with_object(recognizer, Kind::Recognizer, function, |_| ()).is_some()
+        && state::with_object(spk_model, Kind::SpeakerModel, function, |_| ()).is_some()
+    {
+        state::record(Call::SetSpeakerModel);
+    }
+}
+
+#[no_mangle]
+unsafe extern "C" fn vosk_recognizer_set_grm(
+    recognizer: *mut VoskRecognizer,
+    grammar: *const c_char,
+) {
+    let function = "vosk_recognizer_set_grm";
+    if let Some(grammar) = read_string(grammar, function) {
+        set_flag(recognizer, function, Call::SetGrammar { grammar });
+    }
+}
+
+#[no_mangle]
+unsafe extern "C" fn vosk_recognizer_set_max_alternatives(
+    recognizer: *mut VoskRecognizer,
+    max_alternatives: c_int,
+) {
+    set_flag(
+        recognizer,
+        "vosk_recognizer_set_max_alternatives",
+        Call::SetMaxAlternatives(max_alternatives),
+    );
+}
+
+#[no_mangle]
+unsafe extern "C" fn vosk_recognizer_set_words(recognizer: *mut VoskRecognizer, words: c_int) {
+    set_flag(
+        recognizer,
+        "vosk_recognizer_set_words",
+        Call::SetWords(words != 0),
+    );
+}
+
+#[no_mangle]
+unsafe extern "C" fn vosk_recognizer_set_partial_words(
+    recognizer: *mut VoskRecognizer,
+    partial_words: c_int,
+) {
+    set_flag(
+        recognizer,
+        "vosk_recognizer_set_partial_words",
+        Call::SetPartialWords(partial_words != 0),
+    );
+}
+
+#[no_mangle]
+unsafe extern "C" fn vosk_recognizer_set_nlsml(recognizer: *mut VoskRecognizer, nlsml: c_int) {
+    set_flag(
+        recognizer,
+        "vosk_recognizer_set_nlsml",
+        Call::SetNlsml(nlsml != 0),
+    );
+}
+
+#[no_mangle]
+unsafe extern "C" fn vosk_recognizer_set_endpointer_mode(
+    recognizer: *mut VoskRecognizer,
+    mode: VoskEndpointerMode,
+) {
+    set_flag(
+        recognizer,
+        "vosk_recognizer_set_endpointer_mode",
+        Call::SetEndpointerMode(mode),
+    );
+}
+
+#[no_mangle]
+unsafe extern "C" fn vosk_recognizer_set_endpointer_delays(
+    recognizer: *mut VoskRecognizer,
+    t_start_max: f32,
+    t_end: f32,
+    t_max: f32,
+) {
+    set_flag(
+        recognizer,
+        "vosk_recognizer_set_endpointer_delays",
+        Call::SetEndpointerDelays {
+            t_start_max,
+            t_end,
+            t_max,
+        },
+    );
+}
+
+/// Records an `accept_waveform` call and returns the next scripted state.
+fn accept_waveform(recognizer: *mut VoskRecognizer, function: &str, call: Call) -> c_int {
+    match state::with_object(recognizer, Kind::Recognizer, function, |_| ()) {
+        Some(()) => {
+            state::record(call);
+            state::next_decoding_state().to_c_int()
+        }
+        None => -1,
+    }
+}
+
+#[no_mangle]
+unsafe extern "C" fn vosk_recognizer_accept_waveform(
+    recognizer: *mut VoskRecognizer,
+    data: *const c_char,
+    length: c_int,
+) -> c_int {
+    let function = "vosk_recognizer_accept_waveform";
+    let data = read_buffer(data.cast::<u8>(), length, function);
+    accept_waveform(recognizer, function, Call::AcceptWaveform(data))
+}
+
+#[no_mangle]
+unsafe extern "C" fn vosk_recognizer_accept_waveform_s(
+    recognizer: *mut VoskRecognizer,
+    data: *const c_short,
+    length: c_int,
+) -> c_int {
+    let function = "vosk_recognizer_accept_waveform_s";
+    let data = read_buffer(data, length, function);
+    accept_waveform(recognizer, function, Call::AcceptWaveformShort(data))
+}
+
+#[no_mangle]
+unsafe extern "C" fn vosk_recognizer_accept_waveform_f(
+    recognizer: *mut VoskRecognizer,
+    data: *const f32,
+    length: c_int,
+) -> c_int {
+    let function = "vosk_recognizer_accept_waveform_f";
+    let data = read_buffer(data, length, function);
+    accept_waveform(recognizer, function, Call::AcceptWaveformFloat(data))
+}
+
+#[no_mangle]
+unsafe extern "C" fn vosk_recognizer_result(recognizer: *mut VoskRecognizer) -> *const c_char {
+    state::record(Call::Result);
+    store_result(
+        recognizer,
+        Kind::Recognizer,
+        "vosk_recognizer_result",
+        state::next_result(),
+    )
+}
+
+#[no_mangle]
+unsafe extern "C" fn vosk_recognizer_partial_result(
+    recognizer: *mut VoskRecognizer,
+) -> *const c_char {
+    state::record(Call::PartialResult);
+    store_result(
+        recognizer,
+        Kind::Recognizer,
+        "vosk_recognizer_partial_result",
+        state::next_partial_result(),
+    )
+}
+
+#[no_mangle]
+unsafe extern "C" fn vosk_recognizer_final_result(
+    recognizer: *mut VoskRecognizer,
+) -> *const c_char {
+    state::record(Call::FinalResult);
+    store_result(
+        recognizer,
+        Kind::Recognizer,
+        "vosk_recognizer_final_result",
+        state::next_final_result(),
+    )
+}
+
+#[no_mangle]
+unsafe extern "C" fn vosk_recognizer_reset(recognizer: *mut VoskRecognizer) {
+    set_flag(recognizer, "vosk_recognizer_reset", Call::Reset);
+}
+
+#[no_mangle]
+unsafe extern "C" fn vosk_recognizer_free(recognizer: *mut VoskRecognizer) {
+    state::record(Call::RecognizerFree);
+    state::free(recognizer, Kind::Recognizer, "vosk_recognizer_free");
+}
+
+#[no_mangle]
+unsafe extern "C" fn vosk_set_log_level(log_level: c_int) {
+    state::record(Call::SetLogLevel(log_level));
+}
+
+#[no_mangle]
+unsafe extern "C" fn vosk_gpu_init() {
+    state::record(Call::GpuInit);
+}
+
+#[no_mangle]
+unsafe extern "C" fn vosk_gpu_thread_init() {
+    state::record(Call::GpuThreadInit);
+}
+
+#[no_mangle]
+unsafe extern "C" fn vosk_batch_model_new(model_path: *const c_char) -> *mut VoskBatchModel {
+    let Some(path) = read_string(model_path, "vosk_batch_model_new") else {
+        return ptr::null_mut();
+    };
+
+    state::record(Call::BatchModelNew { path });
+    state::create(Kind::BatchModel)
+}
+
+#[no_mangle]
+unsafe extern "C" fn vosk_batch_model_free(model: *mut VoskBatchModel) {
+    state::record(Call::BatchModelFree);
+    state::free(model, Kind::BatchModel, "vosk_batch_model_free");
+}
+
+#[no_mangle]
+unsafe extern "C" fn vosk_batch_model_wait(model: *mut VoskBatchModel) {
+    if state::with_object(model, Kind::BatchModel, "vosk_batch_model_wait", |_| ()).is_some() {
+        state::record(Call::BatchModelWait);
+    }
+}
+
+#[no_mangle]
+unsafe extern "C" fn vosk_batch_recognizer_new(
+    model: *mut VoskBatchModel,
+    sample_rate: f32,
+) -> *mut VoskBatchRecognizer {
+    state::record(Call::BatchRecognizerNew { sample_rate });
+
+    match state::with_object(model, Kind::BatchModel, "vosk_batch_recognizer_new", |_| ()) {
+        Some(()) => state::create(Kind::BatchRecognizer),
+        None => ptr::null_mut(),
+    }
+}
+
+#[no_mangle]
+unsafe extern "C" fn vosk_batch_recognizer_free(recognizer: *mut VoskBatchRecognizer) {
+    state::record(Call::BatchRecognizerFree);
+    state::free(
+        recognizer,
+        Kind::BatchRecognizer,
+        "vosk_batch_recognizer_free",
+    );
+}
+
+/// Records a call on a batch recognizer if it is valid, and returns whether it is.
+fn batch_call(recognizer: *mut VoskBatchRecognizer, function: &str, call: Call) -> bool {
+    let valid = state::with_object(recognizer, Kind::BatchRecognizer, function, |_| ()).is_some();
+    if valid {
+        state::record(call);
+    }
+    valid
+}
+
+#[no_mangle]
+unsafe extern "C" fn vosk_batch_recognizer_accept_waveform(
+    recognizer: *mut VoskBatchRecognizer,
+    data: *const c_char,
+    length: c_int,
+) {
+    let function = "vosk_batch_recognizer_accept_waveform";
+    let data = read_buffer(data.cast::<u8>(), length, function);
+    batch_call(recognizer, function, Call::BatchAcceptWaveform(data));
+}
+
+#[no_mangle]
+unsafe extern "C" fn vosk_batch_recognizer_set_nlsml(
+    recognizer: *mut VoskBatchRecognizer,
+    nlsml: c_int,
+) {
+    batch_call(
+        recognizer,
+        "vosk_batch_recognizer_set_nlsml",
+        Call::BatchSetNlsml(nlsml != 0),
+    );
+}
+
+#[no_mangle]
+unsafe extern "C" fn vosk_batch_recognizer_finish_stream(recognizer: *mut VoskBatchRecognizer) {
+    batch_call(
+        recognizer,
+        "vosk_batch_recognizer_finish_stream",
+        Call::BatchFinishStream,
+    );
+}
+
+#[no_mangle]
+unsafe extern "C" fn vosk_batch_recognizer_front_result(
+    recognizer: *mut VoskBatchRecognizer,
+) -> *const c_char {
+    state::record(Call::BatchFrontResult);
+    store_result(
+        recognizer,
+        Kind::BatchRecognizer,
+        "vosk_batch_recognizer_front_result",
+        state::front_batch_result(),
+    )
+}
+
+#[no_mangle]
+unsafe extern "C" fn vosk_batch_recognizer_pop(recognizer: *mut VoskBatchRecognizer) {
+    if batch_call(recognizer, "vosk_batch_recognizer_pop", Call::BatchPop) {
+        state::pop_batch_result();
+    }
+}
+
+#[no_mangle]
+unsafe extern "C" fn vosk_batch_recognizer_get_pending_chunks(
+    recognizer: *mut VoskBatchRecognizer,
+) -> c_int {
+    let function = "vosk_batch_recognizer_get_pending_chunks";
+
+    if batch_call(recognizer, function, Call::BatchGetPendingChunks) {
+        c_int::try_from(state::pending_batch_results()).unwrap_or(c_int::MAX)
+    } else {
+        0
+    }
+}
+
+#[no_mangle]
+unsafe extern "C" fn vosk_text_processor_new(
+    tagger: *const c_char,
+    verbalizer: *const c_char,
+) -> *mut VoskTextProcessor {
+    let function = "vosk_text_processor_new";
+    let (Some(tagger), Some(verbalizer)) = (
+        read_string(tagger, function),
+        read_string(verbalizer, function),
+    ) else {
+        return ptr::null_mut();
+    };
+
+    state::record(Call::TextProcessorNew { tagger, verbalizer });
+    state::create(Kind::TextProcessor)
+}
+
+#[no_mangle]
+unsafe extern "C" fn vosk_text_processor_free(processor: *mut VoskTextProcessor) {
+    state::record(Call::TextProcessorFree);
+    state::free(processor, Kind::TextProcessor, "vosk_text_processor_free");
+}
+
+#[no_mangle]
+unsafe extern "C" fn vosk_text_processor_itn(
+    processor: *mut VoskTextProcessor,
+    input: *const c_char,
+) -> *mut c_char {
+    let function = "vosk_text_processor_itn";
+    let Some(input) = read_string(input, function) else {
+        return ptr::null_mut();
+    };
+
+    if state::with_object(processor, Kind::TextProcessor, function, |_| ()).is_none() {
+        return ptr::null_mut();
+    }
+
+    let output = state::itn(&input);
+    state::record(Call::Itn { input });
+
+    // The caller frees the string with free, so it is allocated with malloc like Vosk does
+    let output_ptr = libc::malloc(output.len() + 1).cast::<c_char>();
+    if !output_ptr.is_null() {
+        ptr::copy_nonoverlapping(output.as_ptr().cast(), output_ptr, output.len());
+        *output_ptr.add(output.len()) = 0;
+    }
+
+    output_ptr
+}
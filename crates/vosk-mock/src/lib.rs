@@ -0,0 +1,188 @@
+#![deny(missing_docs)]
+
+//! Fake implementation of the Vosk API, for testing code that uses [`vosk-sys`] or [`vosk`]
+//! without the real library or a model.
+//!
+//! Depending on this crate enables the `mock` feature of `vosk-sys`, so libvosk is not linked,
+//! and the `vosk_*` functions are provided by this crate instead. They:
+//! * Return the results scripted with [`script_result`] and friends, or empty results.
+//! * Record every call they receive, which can be inspected with [`calls`] or [`take_calls`].
+//! * Keep track of the objects they create, so objects that are never freed ([`live_objects`]),
+//!   or that are used after being freed or freed twice ([`misuses`]), can be detected.
+//!
+//! The scripted results, recorded calls and misuses are kept per thread, so tests running in
+//! parallel do not interfere with each other. They are only seen by the thread that makes the
+//! calls, even when the objects were created in another thread.
+//!
+//! # Examples
+//!
+//! ```
+//! use vosk::{Model, Recognizer};
+//! use vosk_mock::{Call, DecodingState, FakeModelDir};
+//!
+//! let model_dir = FakeModelDir::new();
+//! let model = Model::new(model_dir.path()).unwrap();
+//! let mut recognizer = Recognizer::new(&model, 16000.0).unwrap();
+//!
+//! vosk_mock::script_decoding_state(DecodingState::Finalized);
+//! vosk_mock::script_result(r#"{"text": "hello world"}"#);
+//!
+//! recognizer.set_words(true);
+//! assert_eq!(
+//!     recognizer.accept_waveform(&[0; 4]).unwrap(),
+//!     vosk::DecodingState::Finalized
+//! );
+//! assert_eq!(recognizer.result().single().unwrap().text, "hello world");
+//!
+//! assert!(vosk_mock::calls().contains(&Call::SetWords(true)));
+//! drop(recognizer);
+//! drop(model);
+//! assert_eq!(vosk_mock::live_objects(), 0);
+//! ```
+//!
+//! [`vosk-sys`]: https://docs.rs/vosk-sys
+//! [`vosk`]: https://docs.rs/vosk
+
+mod ffi;
+mod model_dir;
+mod state;
+
+pub use model_dir::FakeModelDir;
+pub use state::{
+    calls, live_objects, misuses, reset, script_batch_result, script_decoding_state,
+    script_final_result, script_itn, script_partial_result, script_result, set_fail_creation,
+    set_vocabulary, take_calls, DecodingState,
+};
+
+/// A call received by one of the fake `vosk_*` functions, with its arguments.
+///
+/// Calls on models, recognizers and other objects do not include the object itself.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Call {
+    /// `vosk_model_new`.
+    ModelNew {
+        /// Path to the model.
+        path: String,
+    },
+    /// `vosk_model_free`.
+    ModelFree,
+    /// `vosk_model_find_word`.
+    FindWord {
+        /// Word that was looked up.
+        word: String,
+    },
+    /// `vosk_spk_model_new`.
+    SpeakerModelNew {
+        /// Path to the model.
+        path: String,
+    },
+    /// `vosk_spk_model_free`.
+    SpeakerModelFree,
+    /// `vosk_recognizer_new`.
+    RecognizerNew {
+        /// Sample rate of the audio.
+        sample_rate: f32,
+    },
+    /// `vosk_recognizer_new_spk`.
+    RecognizerNewSpeaker {
+        /// Sample rate of the audio.
+        sample_rate: f32,
+    },
+    /// `vosk_recognizer_new_grm`.
+    RecognizerNewGrammar {
+        /// Sample rate of the audio.
+        sample_rate: f32,
+        /// Grammar, as the JSON string Vosk received.
+        grammar: String,
+    },
+    /// `vosk_recognizer_set_spk_model`.
+    SetSpeakerModel,
+    /// `vosk_recognizer_set_grm`.
+    SetGrammar {
+        /// Grammar, as the JSON string Vosk received.
+        grammar: String,
+    },
+    /// `vosk_recognizer_set_max_alternatives`.
+    SetMaxAlternatives(i32),
+    /// `vosk_recognizer_set_words`.
+    SetWords(bool),
+    /// `vosk_recognizer_set_partial_words`.
+    SetPartialWords(bool),
+    /// `vosk_recognizer_set_nlsml`.
+    SetNlsml(bool),
+    /// `vosk_recognizer_set_endpointer_mode`, with the mode as a C enum value.
+    SetEndpointerMode(u32),
+    /// `vosk_recognizer_set_endpointer_delays`.
+    SetEndpointerDelays {
+        /// Initial silence timeout, in seconds.
+        t_start_max: f32,
+        /// Trailing silence timeout, in seconds.
+        t_end: f32,
+        /// Maximum utterance length, in seconds.
+        t_max: f32,
+    },
+    /// `vosk_recognizer_accept_waveform`, with the bytes it received.
+    AcceptWaveform(Vec<u8>),
+    /// `vosk_recognizer_accept_waveform_s`, with the samples it received.
+    AcceptWaveformShort(Vec<i16>),
+    /// `vosk_recognizer_accept_waveform_f`, with the samples it received.
+    AcceptWaveformFloat(Vec<f32>),
+    /// `vosk_recognizer_result`.
+    Result,
+    /// `vosk_recognizer_partial_result`.
+    PartialResult,
+    /// `vosk_recognizer_final_result`.
+    FinalResult,
+    /// `vosk_recognizer_reset`.
+    Reset,
+    /// `vosk_recognizer_free`.
+    RecognizerFree,
+    /// `vosk_set_log_level`.
+    SetLogLevel(i32),
+    /// `vosk_gpu_init`.
+    GpuInit,
+    /// `vosk_gpu_thread_init`.
+    GpuThreadInit,
+    /// `vosk_batch_model_new`.
+    BatchModelNew {
+        /// Path to the model.
+        path: String,
+    },
+    /// `vosk_batch_model_free`.
+    BatchModelFree,
+    /// `vosk_batch_model_wait`.
+    BatchModelWait,
+    /// `vosk_batch_recognizer_new`.
+    BatchRecognizerNew {
+        /// Sample rate of the audio.
+        sample_rate: f32,
+    },
+    /// `vosk_batch_recognizer_free`.
+    BatchRecognizerFree,
+    /// `vosk_batch_recognizer_accept_waveform`, with the bytes it received.
+    BatchAcceptWaveform(Vec<u8>),
+    /// `vosk_batch_recognizer_set_nlsml`.
+    BatchSetNlsml(bool),
+    /// `vosk_batch_recognizer_finish_stream`.
+    BatchFinishStream,
+    /// `vosk_batch_recognizer_front_result`.
+    BatchFrontResult,
+    /// `vosk_batch_recognizer_pop`.
+    BatchPop,
+    /// `vosk_batch_recognizer_get_pending_chunks`.
+    BatchGetPendingChunks,
+    /// `vosk_text_processor_new`.
+    TextProcessorNew {
+        /// Path to the tagger FST.
+        tagger: String,
+        /// Path to the verbalizer FST.
+        verbalizer: String,
+    },
+    /// `vosk_text_processor_free`.
+    TextProcessorFree,
+    /// `vosk_text_processor_itn`.
+    Itn {
+        /// Text that was converted.
+        input: String,
+    },
+}
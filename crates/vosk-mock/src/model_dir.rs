@@ -0,0 +1,135 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    process,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// Temporary directory laid out like a model, so it passes the checks `vosk` makes before
+/// loading a model. The files are empty, except for the configuration.
+///
+/// The directory is deleted when this is dropped.
+///
+/// # Examples
+///
+/// ```
+/// use vosk::Model;
+/// use vosk_mock::FakeModelDir;
+///
+/// let model_dir = FakeModelDir::new();
+/// let model = Model::new(model_dir.path()).unwrap();
+/// ```
+#[derive(Debug)]
+pub struct FakeModelDir {
+    path: PathBuf,
+}
+
+impl FakeModelDir {
+    /// Creates a model with a lookahead graph, which supports grammars.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the directory cannot be created.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::with_files(&[
+            ("am/final.mdl", ""),
+            ("conf/mfcc.conf", "--sample-frequency=16000\n"),
+            ("graph/HCLr.fst", ""),
+            ("graph/Gr.fst", ""),
+        ])
+    }
+
+    /// Creates a model with a static (`HCLG.fst`) graph, which does not support grammars.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the directory cannot be created.
+    #[must_use]
+    pub fn with_static_graph() -> Self {
+        Self::with_files(&[
+            ("am/final.mdl", ""),
+            ("conf/mfcc.conf", "--sample-frequency=16000\n"),
+            ("graph/HCLG.fst", ""),
+        ])
+    }
+
+    /// Creates a speaker identification model.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the directory cannot be created.
+    #[must_use]
+    pub fn speaker() -> Self {
+        Self::with_files(&[
+            ("mfcc.conf", "--sample-frequency=16000\n"),
+            ("final.ext.raw", ""),
+            ("mean.vec", ""),
+            ("transform.mat", ""),
+        ])
+    }
+
+    /// Creates a directory with the given files, as pairs of paths relative to it and contents.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the directory cannot be created.
+    #[must_use]
+    pub fn with_files(files: &[(&str, &str)]) -> Self {
+        let path = std::env::temp_dir().join(format!(
+            "vosk-mock-{}-{}",
+            process::id(),
+            NEXT_ID.fetch_add(1, Ordering::Relaxed)
+        ));
+
+        Self::create(&path, files)
+            .unwrap_or_else(|error| panic!("could not create {}: {error}", path.display()));
+
+        Self { path }
+    }
+
+    fn create(path: &Path, files: &[(&str, &str)]) -> io::Result<()> {
+        fs::create_dir_all(path)?;
+
+        for (file, contents) in files {
+            let file = path.join(file);
+            if let Some(parent) = file.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(file, contents)?;
+        }
+
+        Ok(())
+    }
+
+    /// Path to the directory.
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Writes a file in the directory, creating its parent directories if needed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the file cannot be written.
+    pub fn write(&self, file: &str, contents: &str) {
+        Self::create(&self.path, &[(file, contents)])
+            .unwrap_or_else(|error| panic!("could not write {file}: {error}"));
+    }
+}
+
+impl Default for FakeModelDir {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for FakeModelDir {
+    fn drop(&mut self) {
+        // Leaving the directory behind is harmless
+        let _ = fs::remove_dir_all(&self.path);
+    }
+}
@@ -0,0 +1,354 @@
+//! State of the fake library: the objects it created, and the scripts and calls of each thread.
+
+use crate::Call;
+use std::{
+    cell::RefCell,
+    collections::{HashMap, VecDeque},
+    ffi::CString,
+    os::raw::c_int,
+    sync::Mutex,
+    thread::{self, ThreadId},
+};
+
+/// State returned by the fake `vosk_recognizer_accept_waveform*` functions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DecodingState {
+    /// Decoding continues. Returned when nothing else was scripted.
+    #[default]
+    Running,
+    /// An utterance ended and its result can be retrieved.
+    Finalized,
+    /// Decoding failed.
+    Failed,
+}
+
+impl DecodingState {
+    pub(crate) fn to_c_int(self) -> c_int {
+        match self {
+            Self::Running => 0,
+            Self::Finalized => 1,
+            Self::Failed => -1,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Kind {
+    Model,
+    SpeakerModel,
+    Recognizer,
+    BatchModel,
+    BatchRecognizer,
+    TextProcessor,
+}
+
+impl Kind {
+    fn name(self) -> &'static str {
+        match self {
+            Self::Model => "VoskModel",
+            Self::SpeakerModel => "VoskSpkModel",
+            Self::Recognizer => "VoskRecognizer",
+            Self::BatchModel => "VoskBatchModel",
+            Self::BatchRecognizer => "VoskBatchRecognizer",
+            Self::TextProcessor => "VoskTextProcessor",
+        }
+    }
+}
+
+/// An object handed out by the fake library. Its address is the pointer the caller gets.
+pub(crate) struct Object {
+    kind: Kind,
+    owner: ThreadId,
+    /// Last result returned for the object, which stays valid until the next one.
+    pub(crate) result: CString,
+}
+
+/// Objects of every thread, by address.
+static OBJECTS: Mutex<Vec<(usize, Box<Object>)>> = Mutex::new(Vec::new());
+
+#[derive(Default)]
+struct ThreadState {
+    calls: Vec<Call>,
+    misuses: Vec<String>,
+    results: VecDeque<CString>,
+    partial_results: VecDeque<CString>,
+    final_results: VecDeque<CString>,
+    batch_results: VecDeque<CString>,
+    decoding_states: VecDeque<DecodingState>,
+    vocabulary: Vec<String>,
+    itn: HashMap<String, String>,
+    fail_creation: bool,
+}
+
+thread_local! {
+    static STATE: RefCell<ThreadState> = RefCell::default();
+}
+
+fn with_state<R>(f: impl FnOnce(&mut ThreadState) -> R) -> R {
+    STATE.with(|state| f(&mut state.borrow_mut()))
+}
+
+/// Clears the scripted results, recorded calls, misuses and settings of the current thread.
+///
+/// Objects that were not freed are kept.
+pub fn reset() {
+    with_state(|state| *state = ThreadState::default());
+}
+
+/// Returns the calls the fake library received from the current thread, in order.
+#[must_use]
+pub fn calls() -> Vec<Call> {
+    with_state(|state| state.calls.clone())
+}
+
+/// Like [`calls`], but also clears them.
+#[must_use]
+pub fn take_calls() -> Vec<Call> {
+    with_state(|state| std::mem::take(&mut state.calls))
+}
+
+/// Returns descriptions of the invalid calls received from the current thread, such as calls
+/// with objects that were already freed, objects of the wrong type or NULL strings.
+///
+/// The fake library ignores invalid calls instead of crashing, so they must be checked here.
+#[must_use]
+pub fn misuses() -> Vec<String> {
+    with_state(|state| state.misuses.clone())
+}
+
+/// Returns how many objects (models, recognizers...) created from the current thread have
+/// not been freed yet.
+#[must_use]
+pub fn live_objects() -> usize {
+    let current = thread::current().id();
+    lock_objects()
+        .iter()
+        .filter(|(_, object)| object.owner == current)
+        .count()
+}
+
+/// Adds a JSON result to be returned by `vosk_recognizer_result`.
+///
+/// Results are returned in the order they were added. `{"text": ""}` is returned once
+/// there are none left.
+///
+/// # Panics
+///
+/// Panics if `json` contains a NUL byte, which Vosk could not return.
+pub fn script_result(json: impl Into<String>) {
+    let json = to_c_string(json.into());
+    with_state(|state| state.results.push_back(json));
+}
+
+/// Adds a JSON result to be returned by `vosk_recognizer_partial_result`.
+///
+/// Results are returned in the order they were added. `{"partial": ""}` is returned once
+/// there are none left.
+///
+/// # Panics
+///
+/// Panics if `json` contains a NUL byte, which Vosk could not return.
+pub fn script_partial_result(json: impl Into<String>) {
+    let json = to_c_string(json.into());
+    with_state(|state| state.partial_results.push_back(json));
+}
+
+/// Adds a JSON result to be returned by `vosk_recognizer_final_result`.
+///
+/// Results are returned in the order they were added. `{"text": ""}` is returned once
+/// there are none left.
+///
+/// # Panics
+///
+/// Panics if `json` contains a NUL byte, which Vosk could not return.
+pub fn script_final_result(json: impl Into<String>) {
+    let json = to_c_string(json.into());
+    with_state(|state| state.final_results.push_back(json));
+}
+
+/// Adds a JSON result to the queue read by `vosk_batch_recognizer_front_result`
+/// and `vosk_batch_recognizer_pop`.
+///
+/// An empty string is returned if the queue is empty, like Vosk does.
+///
+/// # Panics
+///
+/// Panics if `json` contains a NUL byte, which Vosk could not return.
+pub fn script_batch_result(json: impl Into<String>) {
+    let json = to_c_string(json.into());
+    with_state(|state| state.batch_results.push_back(json));
+}
+
+/// Adds a state to be returned by the `vosk_recognizer_accept_waveform*` functions.
+///
+/// States are returned in the order they were added. [`DecodingState::Running`] is returned
+/// once there are none left.
+pub fn script_decoding_state(decoding_state: DecodingState) {
+    with_state(|state| state.decoding_states.push_back(decoding_state));
+}
+
+/// Sets the text `vosk_text_processor_itn` converts `input` to.
+/// Other texts are returned unchanged.
+pub fn script_itn(input: impl Into<String>, output: impl Into<String>) {
+    with_state(|state| state.itn.insert(input.into(), output.into()));
+}
+
+/// Sets the words `vosk_model_find_word` knows. Their symbols are their positions in
+/// `words`, starting at 1.
+///
+/// Default: no words.
+pub fn set_vocabulary<S: Into<String>>(words: impl IntoIterator<Item = S>) {
+    let words = words.into_iter().map(Into::into).collect();
+    with_state(|state| state.vocabulary = words);
+}
+
+/// Makes every `vosk_*_new` function return NULL while `fail` is `true`.
+///
+/// Default: `false`.
+pub fn set_fail_creation(fail: bool) {
+    with_state(|state| state.fail_creation = fail);
+}
+
+pub(crate) fn record(call: Call) {
+    with_state(|state| state.calls.push(call));
+}
+
+pub(crate) fn record_misuse(message: String) {
+    with_state(|state| state.misuses.push(message));
+}
+
+pub(crate) fn fail_creation() -> bool {
+    with_state(|state| state.fail_creation)
+}
+
+/// Converts a scripted result to the string Vosk would return.
+fn to_c_string(json: String) -> CString {
+    // Checked when scripting rather than when returning the result,
+    // as the fake functions are called from C and must not panic
+    CString::new(json).unwrap_or_else(|error| {
+        panic!(
+            "scripted results cannot contain NUL bytes, found one at {}",
+            error.nul_position()
+        )
+    })
+}
+
+/// Result returned when none is scripted.
+fn default_result(json: &'static str) -> CString {
+    // UNWRAP: The default results contain no NUL bytes
+    CString::new(json).unwrap()
+}
+
+pub(crate) fn next_result() -> CString {
+    with_state(|state| state.results.pop_front())
+        .unwrap_or_else(|| default_result(r#"{"text": ""}"#))
+}
+
+pub(crate) fn next_partial_result() -> CString {
+    with_state(|state| state.partial_results.pop_front())
+        .unwrap_or_else(|| default_result(r#"{"partial": ""}"#))
+}
+
+pub(crate) fn next_final_result() -> CString {
+    with_state(|state| state.final_results.pop_front())
+        .unwrap_or_else(|| default_result(r#"{"text": ""}"#))
+}
+
+pub(crate) fn front_batch_result() -> CString {
+    with_state(|state| state.batch_results.front().cloned()).unwrap_or_default()
+}
+
+pub(crate) fn pop_batch_result() {
+    with_state(|state| state.batch_results.pop_front());
+}
+
+pub(crate) fn pending_batch_results() -> usize {
+    with_state(|state| state.batch_results.len())
+}
+
+pub(crate) fn next_decoding_state() -> DecodingState {
+    with_state(|state| state.decoding_states.pop_front()).unwrap_or_default()
+}
+
+pub(crate) fn find_word(word: &str) -> Option<usize> {
+    with_state(|state| state.vocabulary.iter().position(|other| other == word)).map(|i| i + 1)
+}
+
+pub(crate) fn itn(input: &str) -> String {
+    with_state(|state| state.itn.get(input).cloned()).unwrap_or_else(|| input.to_owned())
+}
+
+fn lock_objects() -> std::sync::MutexGuard<'static, Vec<(usize, Box<Object>)>> {
+    // A panic while the lock is held cannot leave the list in an invalid state
+    OBJECTS
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Creates an object and returns its address, or NULL if creation is set to fail.
+pub(crate) fn create<T>(kind: Kind) -> *mut T {
+    if fail_creation() {
+        return std::ptr::null_mut();
+    }
+
+    let object = Box::new(Object {
+        kind,
+        owner: thread::current().id(),
+        result: CString::default(),
+    });
+    let address = &*object as *const Object as usize;
+    lock_objects().push((address, object));
+
+    address as *mut T
+}
+
+/// Frees the object at `ptr`, recording a misuse if there is none of the right kind.
+pub(crate) fn free<T>(ptr: *mut T, kind: Kind, function: &str) {
+    let mut objects = lock_objects();
+    let address = ptr as usize;
+
+    match objects.iter().position(|(other, _)| *other == address) {
+        Some(index) if objects[index].1.kind == kind => {
+            objects.remove(index);
+        }
+        _ => {
+            drop(objects);
+            record_misuse(invalid_object(ptr, kind, function));
+        }
+    }
+}
+
+/// Calls `f` with the object at `ptr`, or records a misuse and returns [`None`]
+/// if there is none of the right kind.
+pub(crate) fn with_object<T, R>(
+    ptr: *mut T,
+    kind: Kind,
+    function: &str,
+    f: impl FnOnce(&mut Object) -> R,
+) -> Option<R> {
+    let mut objects = lock_objects();
+    let address = ptr as usize;
+
+    match objects
+        .iter_mut()
+        .find(|(other, object)| *other == address && object.kind == kind)
+    {
+        Some((_, object)) => Some(f(object)),
+        None => {
+            drop(objects);
+            record_misuse(invalid_object(ptr, kind, function));
+            None
+        }
+    }
+}
+
+fn invalid_object<T>(ptr: *mut T, kind: Kind, function: &str) -> String {
+    if ptr.is_null() {
+        format!("{function}: NULL {}", kind.name())
+    } else {
+        format!(
+            "{function}: {ptr:?} is not a live {} (freed already?)",
+            kind.name()
+        )
+    }
+}
@@ -0,0 +1,231 @@
+//! Locates libvosk and links it.
+//!
+//! The library is looked up, in order:
+//! * In `VOSK_LIB_DIR`, if it is set.
+//! * With `pkg-config`.
+//! * In the directories of `LIBRARY_PATH` (and `LIB` and `PATH` on Windows) and
+//!   the `-L` flags of `RUSTFLAGS`.
+//! * In the system library directories, unless cross compiling.
+//!
+//! Setting `VOSK_STATIC` (to anything but `0`) links the static library instead.
+//!
+//! Unless it is found in a system directory, the directory it was found in is exposed to the
+//! build scripts of dependent crates as `DEP_VOSK_LIB_DIR`, so they can add it to the rpath.
+
+use std::{
+    env,
+    path::{Path, PathBuf},
+    process,
+};
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-env-changed=VOSK_LIB_DIR");
+    println!("cargo:rerun-if-env-changed=VOSK_STATIC");
+    println!("cargo:rerun-if-env-changed=LIBRARY_PATH");
+
+    // docs.rs does not have the library, it is loaded at runtime with the dynamic feature,
+    // and vosk-mock provides its functions with the mock feature
+    if env::var_os("DOCS_RS").is_some()
+        || env::var_os("CARGO_FEATURE_DYNAMIC").is_some()
+        || env::var_os("CARGO_FEATURE_MOCK").is_some()
+    {
+        return;
+    }
+
+    let target = Target::from_env();
+
+    if let Some(dir) = env::var_os("VOSK_LIB_DIR") {
+        let dir = PathBuf::from(dir);
+
+        if !has_library(&target, &dir) {
+            fail(&format!(
+                "VOSK_LIB_DIR is set to {}, but it does not contain {}.",
+                dir.display(),
+                target.library_files().join(" or "),
+            ));
+        }
+
+        link(&target, &dir, true);
+        return;
+    }
+
+    if let Ok(library) = pkg_config::Config::new()
+        .statik(target.link_static)
+        .probe("vosk")
+    {
+        // pkg-config already told cargo how to link the library
+        if let Some(dir) = library.link_paths.first().filter(|_| !target.link_static) {
+            println!("cargo:lib_dir={}", dir.display());
+        }
+        return;
+    }
+
+    let search_dirs = search_dirs(&target);
+    if let Some(dir) = search_dirs.iter().find(|dir| has_library(&target, dir)) {
+        link(&target, dir, true);
+        return;
+    }
+
+    let system_dirs = target.system_dirs();
+    if let Some(dir) = system_dirs.iter().find(|dir| has_library(&target, dir)) {
+        link(&target, dir, false);
+        return;
+    }
+
+    let searched: Vec<_> = search_dirs
+        .iter()
+        .chain(&system_dirs)
+        .map(|dir| format!("  - {}", dir.display()))
+        .collect();
+
+    fail(&format!(
+        "Could not find {library}.\n\
+        \n\
+        Download it from https://github.com/alphacep/vosk-api/releases and either:\n\
+        - Set VOSK_LIB_DIR to the directory that contains it.\n\
+        - Install it with a pkg-config file named vosk.pc.\n\
+        - Add its directory to LIBRARY_PATH.\n\
+        \n\
+        Alternatively, enable the `dynamic` feature to load it at runtime instead.\n\
+        \n\
+        Searched in:\n{searched}",
+        library = target.library_files().join(" or "),
+        searched = if searched.is_empty() {
+            "  (no directories)".to_owned()
+        } else {
+            searched.join("\n")
+        },
+    ));
+}
+
+/// What is being linked and for which platform.
+struct Target {
+    os: String,
+    arch: String,
+    cross_compiling: bool,
+    link_static: bool,
+}
+
+impl Target {
+    fn from_env() -> Self {
+        Self {
+            os: env::var("CARGO_CFG_TARGET_OS").unwrap_or_default(),
+            arch: env::var("CARGO_CFG_TARGET_ARCH").unwrap_or_default(),
+            cross_compiling: env::var("HOST").ok() != env::var("TARGET").ok(),
+            link_static: env::var("VOSK_STATIC").is_ok_and(|value| value != "0"),
+        }
+    }
+
+    /// Name of the library, as passed to the linker.
+    fn library_name(&self) -> &'static str {
+        if self.os == "windows" {
+            "libvosk"
+        } else {
+            "vosk"
+        }
+    }
+
+    /// Names of the files any of which means the library is in a directory.
+    fn library_files(&self) -> &'static [&'static str] {
+        match (self.os.as_str(), self.link_static) {
+            ("windows", _) => &["libvosk.lib"],
+            (_, true) => &["libvosk.a"],
+            ("macos" | "ios", false) => &["libvosk.dylib"],
+            (_, false) => &["libvosk.so"],
+        }
+    }
+
+    fn system_dirs(&self) -> Vec<PathBuf> {
+        if self.cross_compiling || self.os == "windows" {
+            return Vec::new();
+        }
+
+        let mut dirs = vec![
+            PathBuf::from("/usr/local/lib"),
+            PathBuf::from("/usr/lib"),
+            PathBuf::from("/usr/lib64"),
+            PathBuf::from(format!("/usr/lib/{}-linux-gnu", self.arch)),
+        ];
+
+        if self.os == "macos" {
+            dirs.push(PathBuf::from("/opt/homebrew/lib"));
+        }
+
+        dirs
+    }
+}
+
+/// Directories the linker is told to search by the user.
+fn search_dirs(target: &Target) -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+
+    // On Windows, the libraries used to be placed in a directory of the PATH
+    let variables: &[&str] = if target.os == "windows" {
+        &["LIBRARY_PATH", "LIB", "PATH"]
+    } else {
+        &["LIBRARY_PATH"]
+    };
+
+    for variable in variables {
+        if let Some(paths) = env::var_os(variable) {
+            dirs.extend(env::split_paths(&paths));
+        }
+    }
+
+    // CARGO_ENCODED_RUSTFLAGS is separated by 0x1f, and contains the flags
+    // from RUSTFLAGS as well as from the cargo configuration
+    let rustflags = env::var("CARGO_ENCODED_RUSTFLAGS").unwrap_or_default();
+    let mut flags = rustflags.split('\x1f');
+
+    while let Some(flag) = flags.next() {
+        let dir = match flag {
+            "-L" => flags.next().unwrap_or_default(),
+            _ => match flag.strip_prefix("-L") {
+                Some(dir) => dir,
+                None => continue,
+            },
+        };
+
+        // Strip the kind from "-L native=/path" and the like
+        let dir = match dir.split_once('=') {
+            Some(("native" | "dependency" | "crate" | "framework" | "all", dir)) => dir,
+            _ => dir,
+        };
+
+        if !dir.is_empty() {
+            dirs.push(PathBuf::from(dir));
+        }
+    }
+
+    dirs
+}
+
+fn has_library(target: &Target, dir: &Path) -> bool {
+    target
+        .library_files()
+        .iter()
+        .any(|file| dir.join(file).is_file())
+}
+
+/// Links the library in `dir`. If `rpath` is `true`, the directory is also exposed to
+/// dependent crates so they can add it to the rpath of their executables.
+fn link(target: &Target, dir: &Path, rpath: bool) {
+    let kind = if target.link_static {
+        "static"
+    } else {
+        "dylib"
+    };
+
+    println!("cargo:rustc-link-search=native={}", dir.display());
+    println!("cargo:rustc-link-lib={kind}={}", target.library_name());
+
+    if rpath && !target.link_static {
+        println!("cargo:lib_dir={}", dir.display());
+    }
+}
+
+fn fail(message: &str) -> ! {
+    eprintln!("error: {message}");
+    process::exit(1);
+}
@@ -0,0 +1,134 @@
+//! Loading of libvosk at runtime, used instead of linking it with the `dynamic` feature.
+
+use crate::Functions;
+use libloading::Library;
+use std::{
+    error::Error,
+    fmt,
+    path::{Path, PathBuf},
+    sync::OnceLock,
+};
+
+struct Loaded {
+    functions: Functions,
+    path: PathBuf,
+    // Unloading the library would invalidate the function pointers
+    _library: Library,
+}
+
+static LOADED: OnceLock<Loaded> = OnceLock::new();
+
+/// Possible errors that [`load`] might return.
+#[derive(Debug)]
+pub enum LoadError {
+    /// Error returned if the library could not be opened.
+    Open {
+        /// Path to the library.
+        path: PathBuf,
+        /// Underlying error.
+        source: libloading::Error,
+    },
+
+    /// Error returned if the library does not export every function these bindings need,
+    /// usually because it is an older version of Vosk.
+    ///
+    /// Functions that older versions or some builds of Vosk lack (grammars, endpointer settings,
+    /// text processing, GPU and batch recognition) are optional: they are not listed here,
+    /// and calling them panics if the library does not export them.
+    MissingSymbols {
+        /// Path to the library.
+        path: PathBuf,
+        /// Names of the missing functions.
+        symbols: Vec<&'static str>,
+    },
+
+    /// Error returned if a library was already loaded, since it can only be loaded once
+    /// per process. Contains the path to the loaded library.
+    AlreadyLoaded(PathBuf),
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Open { path, .. } => write!(f, "could not open the vosk library at {path:?}"),
+            Self::MissingSymbols { path, symbols } => write!(
+                f,
+                "the vosk library at {path:?} is missing {}",
+                symbols.join(", ")
+            ),
+            Self::AlreadyLoaded(path) => {
+                write!(f, "the vosk library at {path:?} was already loaded")
+            }
+        }
+    }
+}
+
+impl Error for LoadError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Open { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+/// Loads libvosk from `path` and resolves all of its functions.
+///
+/// It must be called once before any other function of this crate, which panic otherwise.
+/// Optional functions that the library does not export also panic when called
+/// (see [`LoadError::MissingSymbols`]).
+///
+/// The library is never unloaded.
+pub fn load(path: impl AsRef<Path>) -> Result<(), LoadError> {
+    let path = path.as_ref();
+
+    if let Some(loaded) = LOADED.get() {
+        return Err(LoadError::AlreadyLoaded(loaded.path.clone()));
+    }
+
+    // SAFETY: Loading libvosk runs no initialization code with requirements for the caller
+    let library = unsafe { Library::new(path) }.map_err(|source| LoadError::Open {
+        path: path.to_owned(),
+        source,
+    })?;
+
+    // SAFETY: The signatures of the functions are the ones declared by libvosk's header
+    let functions =
+        unsafe { Functions::resolve(&library) }.map_err(|symbols| LoadError::MissingSymbols {
+            path: path.to_owned(),
+            symbols,
+        })?;
+
+    LOADED
+        .set(Loaded {
+            functions,
+            path: path.to_owned(),
+            _library: library,
+        })
+        // UNWRAP: Setting only fails if another thread loaded a library in the meantime
+        .map_err(|_| LoadError::AlreadyLoaded(LOADED.get().unwrap().path.clone()))
+}
+
+/// Returns whether a library was loaded with [`load`].
+pub fn is_loaded() -> bool {
+    LOADED.get().is_some()
+}
+
+#[cfg_attr(feature = "mock", allow(dead_code))]
+pub(crate) fn functions() -> &'static Functions {
+    &loaded().functions
+}
+
+fn loaded() -> &'static Loaded {
+    match LOADED.get() {
+        Some(loaded) => loaded,
+        None => panic!("libvosk was not loaded, call vosk_sys::load (or vosk::load_library) first"),
+    }
+}
+
+/// Panics because the loaded library does not export the optional function `name`.
+#[cfg_attr(feature = "mock", allow(dead_code))]
+pub(crate) fn missing(name: &str) -> ! {
+    let path = &loaded().path;
+    panic!("{name} is not exported by the vosk library at {path:?}, which is likely too old")
+}
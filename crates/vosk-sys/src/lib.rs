@@ -1,8 +1,20 @@
 /* automatically generated by rust-bindgen 0.60.1 */
 
+// The extern block generated by bindgen is wrapped in `functions!`, which either links
+// the functions or loads them at runtime with the `dynamic` feature
+
 #![allow(non_snake_case)]
 #![allow(non_camel_case_types)]
 #![allow(non_upper_case_globals)]
+#![cfg_attr(docsrs, feature(doc_auto_cfg))]
+
+#[macro_use]
+mod macros;
+#[cfg(feature = "dynamic")]
+mod dynamic;
+
+#[cfg(feature = "dynamic")]
+pub use dynamic::{is_loaded, load, LoadError};
 
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
@@ -34,9 +46,20 @@ pub struct VoskBatchRecognizer {
     _unused: [u8; 0],
 }
 
-#[cfg_attr(not(target_os = "windows"), link(name = "vosk"))]
-#[cfg_attr(target_os = "windows", link(name = "libvosk"))]
-extern "C" {
+#[doc = " Inverse text normalization"]
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct VoskTextProcessor {
+    _unused: [u8; 0],
+}
+
+pub const VoskEndpointerMode_VOSK_EP_ANSWER_DEFAULT: VoskEndpointerMode = 0;
+pub const VoskEndpointerMode_VOSK_EP_ANSWER_SHORT: VoskEndpointerMode = 1;
+pub const VoskEndpointerMode_VOSK_EP_ANSWER_LONG: VoskEndpointerMode = 2;
+pub const VoskEndpointerMode_VOSK_EP_ANSWER_VERY_LONG: VoskEndpointerMode = 3;
+pub type VoskEndpointerMode = ::std::os::raw::c_uint;
+
+functions! {
     #[doc = " Loads model data from the file and returns the model object"]
     #[doc = ""]
     #[doc = " @param model_path: the path of the model on the filesystem"]
@@ -291,64 +314,112 @@ extern "C" {
     #[doc = "     greather than 0 - more verbose mode"]
     pub fn vosk_set_log_level(log_level: ::std::os::raw::c_int);
 
-    #[doc = "  Init, automatically select a CUDA device and allow multithreading."]
-    #[doc = "  Must be called once from the main thread."]
-    #[doc = "  Has no effect if HAVE_CUDA flag is not set."]
-    pub fn vosk_gpu_init();
-
-    #[doc = "  Init CUDA device in a multi-threaded environment."]
-    #[doc = "  Must be called for each thread."]
-    #[doc = "  Has no effect if HAVE_CUDA flag is not set."]
-    pub fn vosk_gpu_thread_init();
-
-    #[doc = " Creates the batch recognizer object"]
-    #[doc = ""]
-    #[doc = "  @returns model object or NULL if problem occured"]
-    pub fn vosk_batch_model_new(model_path: *const ::std::os::raw::c_char) -> *mut VoskBatchModel;
-
-    #[doc = " Releases batch model object"]
-    pub fn vosk_batch_model_free(model: *mut VoskBatchModel);
-
-    #[doc = " Wait for the processing"]
-    pub fn vosk_batch_model_wait(model: *mut VoskBatchModel);
-
-    #[doc = " Creates batch recognizer object"]
-    #[doc = "  @returns recognizer object or NULL if problem occured"]
-    pub fn vosk_batch_recognizer_new(
-        model: *mut VoskBatchModel,
-        sample_rate: f32,
-    ) -> *mut VoskBatchRecognizer;
-
-    #[doc = " Releases batch recognizer object"]
-    pub fn vosk_batch_recognizer_free(recognizer: *mut VoskBatchRecognizer);
-
-    #[doc = " Accept batch voice data"]
-    pub fn vosk_batch_recognizer_accept_waveform(
-        recognizer: *mut VoskBatchRecognizer,
-        data: *const ::std::os::raw::c_char,
-        length: ::std::os::raw::c_int,
-    );
-
-    #[doc = " Set NLSML output"]
-    #[doc = " @param nlsml - boolean value"]
-    pub fn vosk_batch_recognizer_set_nlsml(
-        recognizer: *mut VoskBatchRecognizer,
-        nlsml: ::std::os::raw::c_int,
-    );
-
-    #[doc = " Closes the stream"]
-    pub fn vosk_batch_recognizer_finish_stream(recognizer: *mut VoskBatchRecognizer);
-
-    #[doc = " Return results"]
-    pub fn vosk_batch_recognizer_front_result(
-        recognizer: *mut VoskBatchRecognizer,
-    ) -> *const ::std::os::raw::c_char;
-
-    #[doc = " Release and free first retrieved result"]
-    pub fn vosk_batch_recognizer_pop(recognizer: *mut VoskBatchRecognizer);
-
-    #[doc = " Get amount of pending chunks for more intelligent waiting"]
-    pub fn vosk_batch_recognizer_get_pending_chunks(
-        recognizer: *mut VoskBatchRecognizer,
-    ) -> ::std::os::raw::c_int;
+    // Missing from older versions of libvosk, or from builds without GPU or batch support
+    optional {
+        #[doc = " Reconfigures recognizer to use grammar"]
+        #[doc = ""]
+        #[doc = " @param recognizer   Already running VoskRecognizer"]
+        #[doc = " @param grammar      Set of phrases in JSON array of strings or \"\\[\\]\" to use default model graph."]
+        #[doc = "                     See also vosk_recognizer_new_grm"]
+        pub fn vosk_recognizer_set_grm(
+            recognizer: *mut VoskRecognizer,
+            grammar: *const ::std::os::raw::c_char,
+        );
+
+        #[doc = " Set endpointer scaling factor"]
+        #[doc = ""]
+        #[doc = " @param mode Endpointer mode"]
+        pub fn vosk_recognizer_set_endpointer_mode(
+            recognizer: *mut VoskRecognizer,
+            mode: VoskEndpointerMode,
+        );
+
+        #[doc = " Set endpointer delays"]
+        #[doc = ""]
+        #[doc = " @param t_start_max     timeout for stopping recognition in case of initial silence (usually around 5.0)"]
+        #[doc = " @param t_end           timeout for stopping recognition in milliseconds after we recognized something (usually around 0.5 - 1.0)"]
+        #[doc = " @param t_max           timeout for forcing utterance end in milliseconds (usually around 20-30)"]
+        pub fn vosk_recognizer_set_endpointer_delays(
+            recognizer: *mut VoskRecognizer,
+            t_start_max: f32,
+            t_end: f32,
+            t_max: f32,
+        );
+
+        #[doc = "  Init, automatically select a CUDA device and allow multithreading."]
+        #[doc = "  Must be called once from the main thread."]
+        #[doc = "  Has no effect if HAVE_CUDA flag is not set."]
+        pub fn vosk_gpu_init();
+
+        #[doc = "  Init CUDA device in a multi-threaded environment."]
+        #[doc = "  Must be called for each thread."]
+        #[doc = "  Has no effect if HAVE_CUDA flag is not set."]
+        pub fn vosk_gpu_thread_init();
+
+        #[doc = " Creates the batch recognizer object"]
+        #[doc = ""]
+        #[doc = "  @returns model object or NULL if problem occured"]
+        pub fn vosk_batch_model_new(model_path: *const ::std::os::raw::c_char) -> *mut VoskBatchModel;
+
+        #[doc = " Releases batch model object"]
+        pub fn vosk_batch_model_free(model: *mut VoskBatchModel);
+
+        #[doc = " Wait for the processing"]
+        pub fn vosk_batch_model_wait(model: *mut VoskBatchModel);
+
+        #[doc = " Creates batch recognizer object"]
+        #[doc = "  @returns recognizer object or NULL if problem occured"]
+        pub fn vosk_batch_recognizer_new(
+            model: *mut VoskBatchModel,
+            sample_rate: f32,
+        ) -> *mut VoskBatchRecognizer;
+
+        #[doc = " Releases batch recognizer object"]
+        pub fn vosk_batch_recognizer_free(recognizer: *mut VoskBatchRecognizer);
+
+        #[doc = " Accept batch voice data"]
+        pub fn vosk_batch_recognizer_accept_waveform(
+            recognizer: *mut VoskBatchRecognizer,
+            data: *const ::std::os::raw::c_char,
+            length: ::std::os::raw::c_int,
+        );
+
+        #[doc = " Set NLSML output"]
+        #[doc = " @param nlsml - boolean value"]
+        pub fn vosk_batch_recognizer_set_nlsml(
+            recognizer: *mut VoskBatchRecognizer,
+            nlsml: ::std::os::raw::c_int,
+        );
+
+        #[doc = " Closes the stream"]
+        pub fn vosk_batch_recognizer_finish_stream(recognizer: *mut VoskBatchRecognizer);
+
+        #[doc = " Return results"]
+        pub fn vosk_batch_recognizer_front_result(
+            recognizer: *mut VoskBatchRecognizer,
+        ) -> *const ::std::os::raw::c_char;
+
+        #[doc = " Release and free first retrieved result"]
+        pub fn vosk_batch_recognizer_pop(recognizer: *mut VoskBatchRecognizer);
+
+        #[doc = " Get amount of pending chunks for more intelligent waiting"]
+        pub fn vosk_batch_recognizer_get_pending_chunks(
+            recognizer: *mut VoskBatchRecognizer,
+        ) -> ::std::os::raw::c_int;
+
+        #[doc = " Create text processor"]
+        pub fn vosk_text_processor_new(
+            tagger: *const ::std::os::raw::c_char,
+            verbalizer: *const ::std::os::raw::c_char,
+        ) -> *mut VoskTextProcessor;
+
+        #[doc = " Release text processor"]
+        pub fn vosk_text_processor_free(processor: *mut VoskTextProcessor);
+
+        #[doc = " Convert string"]
+        pub fn vosk_text_processor_itn(
+            processor: *mut VoskTextProcessor,
+            input: *const ::std::os::raw::c_char,
+        ) -> *mut ::std::os::raw::c_char;
+    }
 }
@@ -0,0 +1,118 @@
+/// Declares the functions of libvosk.
+///
+/// Without the `dynamic` feature, they are declared in an `extern` block and linked
+/// (see `build.rs`), or provided by vosk-mock with the `mock` feature.
+/// With it, each of them is a wrapper with the same signature that calls the function
+/// resolved by [`load`](crate::load).
+///
+/// The functions in the `optional` block are missing from older versions of libvosk,
+/// or from builds without some of its features. With the `dynamic` feature, loading a library
+/// without them succeeds, and their wrappers panic when called.
+macro_rules! functions {
+    (
+        $(
+            $(#[$attr:meta])*
+            pub fn $name:ident($($arg:ident: $ty:ty),* $(,)?) $(-> $ret:ty)?;
+        )*
+        optional {
+            $(
+                $(#[$optional_attr:meta])*
+                pub fn $optional_name:ident(
+                    $($optional_arg:ident: $optional_ty:ty),* $(,)?
+                ) $(-> $optional_ret:ty)?;
+            )*
+        }
+    ) => {
+        // The library is linked by the build script
+        #[cfg(any(not(feature = "dynamic"), feature = "mock"))]
+        extern "C" {
+            $(
+                $(#[$attr])*
+                pub fn $name($($arg: $ty),*) $(-> $ret)?;
+            )*
+            $(
+                $(#[$optional_attr])*
+                pub fn $optional_name($($optional_arg: $optional_ty),*) $(-> $optional_ret)?;
+            )*
+        }
+
+        /// Pointers to the functions of a loaded library.
+        /// The optional ones are [`None`] if the library does not export them.
+        #[cfg(feature = "dynamic")]
+        #[cfg_attr(feature = "mock", allow(dead_code))]
+        pub(crate) struct Functions {
+            $($name: unsafe extern "C" fn($($ty),*) $(-> $ret)?,)*
+            $(
+                $optional_name:
+                    Option<unsafe extern "C" fn($($optional_ty),*) $(-> $optional_ret)?>,
+            )*
+        }
+
+        #[cfg(feature = "dynamic")]
+        impl Functions {
+            /// Resolves every function in `library`, or returns the names of the missing ones
+            /// that are not optional.
+            pub(crate) unsafe fn resolve(
+                library: &::libloading::Library,
+            ) -> Result<Self, Vec<&'static str>> {
+                let mut missing = Vec::new();
+
+                $(
+                    let $name = library
+                        .get::<unsafe extern "C" fn($($ty),*) $(-> $ret)?>(
+                            concat!(stringify!($name), "\0").as_bytes(),
+                        )
+                        .map(|symbol| *symbol)
+                        .map_err(|_| missing.push(stringify!($name)))
+                        .ok();
+                )*
+
+                if !missing.is_empty() {
+                    return Err(missing);
+                }
+
+                // UNWRAP: Every function that is not optional was resolved
+                Ok(Self {
+                    $($name: $name.unwrap(),)*
+                    $(
+                        $optional_name: library
+                            .get::<unsafe extern "C" fn($($optional_ty),*) $(-> $optional_ret)?>(
+                                concat!(stringify!($optional_name), "\0").as_bytes(),
+                            )
+                            .map(|symbol| *symbol)
+                            .ok(),
+                    )*
+                })
+            }
+        }
+
+        $(
+            $(#[$attr])*
+            #[cfg(all(feature = "dynamic", not(feature = "mock")))]
+            #[allow(clippy::missing_safety_doc)]
+            #[inline]
+            pub unsafe fn $name($($arg: $ty),*) $(-> $ret)? {
+                (crate::dynamic::functions().$name)($($arg),*)
+            }
+        )*
+
+        $(
+            $(#[$optional_attr])*
+            ///
+            /// # Panics
+            ///
+            /// Panics if the loaded library does not export this function.
+            #[cfg(all(feature = "dynamic", not(feature = "mock")))]
+            #[allow(clippy::missing_safety_doc)]
+            #[inline]
+            pub unsafe fn $optional_name(
+                $($optional_arg: $optional_ty),*
+            ) $(-> $optional_ret)? {
+                match crate::dynamic::functions().$optional_name {
+                    Some(function) => function($($optional_arg),*),
+                    None => crate::dynamic::missing(stringify!($optional_name)),
+                }
+            }
+        )*
+    };
+}
@@ -0,0 +1,21 @@
+//! Adds the directory libvosk was found in by vosk-sys to the rpath of the examples
+//! and tests, so they run without setting `LD_LIBRARY_PATH` or copying the library.
+
+use std::env;
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-env-changed=DEP_VOSK_LIB_DIR");
+
+    let Ok(lib_dir) = env::var("DEP_VOSK_LIB_DIR") else {
+        return;
+    };
+
+    // Windows has no rpath, the library must be next to the executable or in the PATH
+    if env::var("CARGO_CFG_TARGET_OS").as_deref() == Ok("windows") {
+        return;
+    }
+
+    println!("cargo:rustc-link-arg-examples=-Wl,-rpath,{lib_dir}");
+    println!("cargo:rustc-link-arg-tests=-Wl,-rpath,{lib_dir}");
+}
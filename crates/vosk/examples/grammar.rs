@@ -9,7 +9,7 @@
 use std::env;
 
 use hound::WavReader;
-use vosk::{DecodingState, Model, Recognizer};
+use vosk::{DecodingState, Grammar, Model, Recognizer};
 
 fn main() {
     let mut args = env::args();
@@ -28,19 +28,25 @@ fn main() {
 
     let model = Model::new(model_path).expect("Could not create the model");
 
-    let mut recognizer = Recognizer::new_with_grammar(
-        &model,
-        reader.spec().sample_rate as f32,
-        // Provide a list of phrases to be recognized.
-        //
-        // If "[unk]" is added, it will be the fallback for any word that could not be recognized.
-        // Otherwise, the best match will be used in the result, even if it is most likely
-        // incorrect.
-        //
-        // Note that the words in a phrase can still be recognized separately
-        &["one two three four five six seven eight nine zero", "[unk]"],
-    )
-    .expect("Could not create the recognizer");
+    // Provide a list of phrases to be recognized.
+    //
+    // If "[unk]" is added with `with_unknown`, it will be the fallback for any word that could not
+    // be recognized. Otherwise, the best match will be used in the result, even if it is most likely
+    // incorrect.
+    //
+    // Note that the words in a phrase can still be recognized separately
+    let grammar = Grammar::new()
+        .phrase("one two three four five six seven eight nine zero")
+        .with_unknown();
+
+    // A phrase with a word unknown to the model would never be recognized
+    grammar
+        .validate(&model)
+        .expect("The grammar contains words unknown to the model");
+
+    let mut recognizer =
+        Recognizer::new_with_grammar(&model, reader.spec().sample_rate as f32, &grammar)
+            .expect("Could not create the recognizer");
 
     for sample in samples.chunks(4000) {
         let state = recognizer.accept_waveform(sample).unwrap();
@@ -13,10 +13,10 @@ use std::{
 
 use cpal::{
     traits::{DeviceTrait, HostTrait, StreamTrait},
-    ChannelCount, SampleFormat,
+    SampleFormat,
 };
-use dasp::{sample::ToSample, Sample};
-use vosk::{DecodingState, Model, Recognizer};
+use dasp::Sample;
+use vosk::{AudioSample, ChannelMix, DecodingState, Recognizer, SharedModel};
 
 fn main() {
     let mut args = env::args();
@@ -39,13 +39,17 @@ fn main() {
         .expect("Failed to load default input config");
     let channels = config.channels();
 
-    let model = Model::new(model_path).expect("Could not create the model");
-    let mut recognizer = Recognizer::new(&model, config.sample_rate().0 as f32)
+    // The stream callbacks must be 'static, so the recognizer has to keep the model alive
+    let model = SharedModel::new(model_path).expect("Could not create the model");
+    let mut recognizer = Recognizer::new(model, config.sample_rate().0 as f32)
         .expect("Could not create the Recognizer");
 
     recognizer.set_max_alternatives(10);
     recognizer.set_words(true);
     recognizer.set_partial_words(true);
+    recognizer
+        .set_input_channels(channels, ChannelMix::Average)
+        .expect("Invalid number of channels");
 
     let recognizer = Arc::new(Mutex::new(recognizer));
 
@@ -57,25 +61,29 @@ fn main() {
     let stream = match config.sample_format() {
         SampleFormat::I8 => audio_input_device.build_input_stream(
             &config.into(),
-            move |data: &[i8], _| recognize(&mut recognizer_clone.lock().unwrap(), data, channels),
+            move |data: &[i8], _| {
+                // 8-bit audio, which `accept_samples` would take as the bytes of 16-bit audio
+                let data: Vec<i16> = data.iter().map(|sample| sample.to_sample()).collect();
+                recognize(&mut recognizer_clone.lock().unwrap(), &data)
+            },
             err_fn,
             None,
         ),
         SampleFormat::I16 => audio_input_device.build_input_stream(
             &config.into(),
-            move |data: &[i16], _| recognize(&mut recognizer_clone.lock().unwrap(), data, channels),
+            move |data: &[i16], _| recognize(&mut recognizer_clone.lock().unwrap(), data),
             err_fn,
             None,
         ),
         SampleFormat::I32 => audio_input_device.build_input_stream(
             &config.into(),
-            move |data: &[i32], _| recognize(&mut recognizer_clone.lock().unwrap(), data, channels),
+            move |data: &[i32], _| recognize(&mut recognizer_clone.lock().unwrap(), data),
             err_fn,
             None,
         ),
         SampleFormat::F32 => audio_input_device.build_input_stream(
             &config.into(),
-            move |data: &[f32], _| recognize(&mut recognizer_clone.lock().unwrap(), data, channels),
+            move |data: &[f32], _| recognize(&mut recognizer_clone.lock().unwrap(), data),
             err_fn,
             None,
         ),
@@ -92,19 +100,8 @@ fn main() {
     println!("{:#?}", recognizer.lock().unwrap().final_result());
 }
 
-fn recognize<T: Sample + ToSample<i16>>(
-    recognizer: &mut Recognizer,
-    data: &[T],
-    channels: ChannelCount,
-) {
-    let data: Vec<i16> = data.iter().map(|v| v.to_sample()).collect();
-    let data = if channels != 1 {
-        stereo_to_mono(&data)
-    } else {
-        data
-    };
-
-    let state = recognizer.accept_waveform(&data).unwrap();
+fn recognize<T: AudioSample>(recognizer: &mut Recognizer, data: &[T]) {
+    let state = recognizer.accept_samples(data).unwrap();
     match state {
         DecodingState::Running => {
             println!("partial: {:#?}", recognizer.partial_result());
@@ -116,14 +113,3 @@ fn recognize<T: Sample + ToSample<i16>>(
         DecodingState::Failed => eprintln!("error"),
     }
 }
-
-pub fn stereo_to_mono(input_data: &[i16]) -> Vec<i16> {
-    let mut result = Vec::with_capacity(input_data.len() / 2);
-    result.extend(
-        input_data
-            .chunks_exact(2)
-            .map(|chunk| chunk[0] / 2 + chunk[1] / 2),
-    );
-
-    result
-}
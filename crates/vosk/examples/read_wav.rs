@@ -9,7 +9,7 @@
 use std::env;
 
 use hound::WavReader;
-use vosk::{Model, Recognizer};
+use vosk::{Event, Model, Recognizer, Transcriber};
 
 fn main() {
     let mut args = env::args();
@@ -21,23 +21,28 @@ fn main() {
         .expect("A path for the wav file to be read was not provided");
 
     let mut reader = WavReader::open(wav_path).expect("Could not create the WAV reader");
+    let sample_rate = reader.spec().sample_rate as f32;
+    // The samples are read as they are recognized
     let samples = reader
-        .samples()
-        .collect::<hound::Result<Vec<i16>>>()
-        .expect("Could not read WAV file");
+        .samples::<i16>()
+        .map(|sample| sample.expect("Could not read WAV file"));
 
     let model = Model::new(model_path).expect("Could not create the model");
-    let mut recognizer = Recognizer::new(&model, reader.spec().sample_rate as f32)
-        .expect("Could not create the recognizer");
+    let mut recognizer =
+        Recognizer::new(&model, sample_rate).expect("Could not create the recognizer");
 
     recognizer.set_max_alternatives(10);
     recognizer.set_words(true);
     recognizer.set_partial_words(true);
 
-    for sample in samples.chunks(100) {
-        recognizer.accept_waveform(sample).unwrap();
-        println!("{:#?}", recognizer.partial_result());
+    for event in Transcriber::new(&mut recognizer, samples) {
+        match event {
+            Event::Partial(partial) => println!("{partial:#?}"),
+            // Results will always be multiple because we called set_max_alternatives
+            Event::Final(result) | Event::EndOfStream(result) => {
+                println!("{:#?}", result.multiple().unwrap())
+            }
+            Event::Failed(error) => eprintln!("Could not recognize a chunk: {error:?}"),
+        }
     }
-
-    println!("{:#?}", recognizer.final_result().multiple().unwrap());
 }
@@ -34,8 +34,8 @@ fn main() {
         Recognizer::new_with_speaker(&model, reader.spec().sample_rate as f32, &spk_model)
             .expect("Could not create the recognizer");
 
-    // Alternatives cannot be enabled as the Alternative objets do not contain the speaker info
-    // recognizer.set_max_alternatives(10);
+    // The speaker info is returned next to the alternatives
+    recognizer.set_max_alternatives(10);
 
     // Words will remain disabled so the speaker data is more visible, though they could be enabled
     // recognizer.set_words(true);
@@ -46,5 +46,5 @@ fn main() {
         println!("{:#?}", recognizer.partial_result());
     }
 
-    println!("{:#?}", recognizer.final_result().single().unwrap());
+    println!("{:#?}", recognizer.final_result().multiple().unwrap());
 }
@@ -0,0 +1,39 @@
+//! Run with:
+//! cargo run --example transcribe_file --features audio <model path> <audio path>
+//! e.g. "cargo run --example transcribe_file --features audio /home/user/stt/model /home/user/stt/test.mp3"
+//!
+//! Read the "Setup" section in the README to know how to link the vosk dynamic
+//! libaries to the examples
+
+use std::env;
+
+use vosk::{transcribe_file, Model, TranscribeOptions};
+
+fn main() {
+    let mut args = env::args();
+    args.next();
+
+    let model_path = args.next().expect("A model path was not provided");
+    let audio_path = args
+        .next()
+        .expect("A path for the audio file to be read was not provided");
+
+    let model = Model::new(model_path).expect("Could not create the model");
+    let options = TranscribeOptions {
+        words: true,
+        ..TranscribeOptions::default()
+    };
+    let transcript =
+        transcribe_file(&model, audio_path, options).expect("Could not transcribe the file");
+
+    for utterance in &transcript.utterances {
+        println!("{utterance:#?}");
+    }
+    println!("{:#?}", transcript.final_result);
+
+    println!(
+        "Transcribed {:?} of audio: {}",
+        transcript.duration,
+        transcript.text()
+    );
+}
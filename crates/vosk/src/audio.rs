@@ -0,0 +1,305 @@
+//! Decoding of audio files, to transcribe them in one call.
+
+use crate::{
+    recognition::{InputChannels, Resampler, Waveform},
+    AcceptWaveformError, ChannelMix, CompleteResult, DecodingState, Model, Recognizer,
+    RecognizerError, ResultError, SpeechRecognizer,
+};
+
+use std::{fs::File, io, path::Path, time::Duration};
+use symphonia::core::{
+    audio::SampleBuffer,
+    codecs::{DecoderOptions, CODEC_TYPE_NULL},
+    errors::Error as SymphoniaError,
+    formats::FormatOptions,
+    io::MediaSourceStream,
+    meta::MetadataOptions,
+    probe::Hint,
+};
+use thiserror::Error;
+
+/// Options of [`transcribe_file`].
+///
+/// # Examples
+///
+/// ```
+/// # use vosk::{ChannelMix, TranscribeOptions};
+/// #
+/// // Only the left channel, with the time of each word
+/// let options = TranscribeOptions {
+///     channel_mix: ChannelMix::Channel(0),
+///     words: true,
+///     ..TranscribeOptions::default()
+/// };
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct TranscribeOptions {
+    /// How the channels of the file are mixed (see [`Recognizer::set_input_channels`]).
+    /// Default: [`ChannelMix::Average`].
+    pub channel_mix: ChannelMix,
+
+    /// Number of alternatives of each result (see [`Recognizer::set_max_alternatives`]).
+    /// Default: 0.
+    pub max_alternatives: u16,
+
+    /// Whether results include the words and their times (see [`Recognizer::set_words`]).
+    /// Default: `false`.
+    pub words: bool,
+}
+
+/// Transcription of an audio file, returned by [`transcribe_file`] and
+/// [`transcribe_file_with`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Transcript {
+    /// Results of the utterances the recognizer finalized, in order.
+    pub utterances: Vec<CompleteResult<'static>>,
+
+    /// Result of the audio after the last finalized utterance
+    /// (see [`SpeechRecognizer::final_result`]).
+    pub final_result: CompleteResult<'static>,
+
+    /// Duration of the decoded audio.
+    pub duration: Duration,
+}
+
+impl Transcript {
+    /// Text of the whole file: the text of every result (see [`CompleteResult::text`])
+    /// that is not empty, separated by spaces.
+    #[must_use]
+    pub fn text(&self) -> String {
+        self.utterances
+            .iter()
+            .chain([&self.final_result])
+            .filter_map(CompleteResult::text)
+            .filter(|text| !text.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// Possible errors that [`transcribe_file`] and [`transcribe_file_with`] might return.
+#[derive(Error, Debug)]
+pub enum TranscribeError {
+    /// Error returned if the file cannot be opened.
+    #[error("could not open the audio file")]
+    Io(#[from] io::Error),
+
+    /// Error returned if the file is not in a supported format, or cannot be decoded.
+    #[error("could not decode the audio file")]
+    Decode(#[from] SymphoniaError),
+
+    /// Error returned if the file does not contain any audio track that can be decoded.
+    #[error("the file does not contain any audio track")]
+    NoAudioTrack,
+
+    /// Error returned if the sample rate of the audio is not known.
+    #[error("the sample rate of the audio is unknown")]
+    UnknownSampleRate,
+
+    /// Error returned if the recognizer cannot be created or configured, or the audio
+    /// cannot be converted for it, such as when [`TranscribeOptions::channel_mix`] selects
+    /// a channel the file does not have.
+    #[error("could not set up the recognizer")]
+    Recognizer(#[from] RecognizerError),
+
+    /// Error returned if the recognizer rejects the decoded audio.
+    #[error("the recognizer did not accept the audio")]
+    AcceptWaveform(#[from] AcceptWaveformError),
+
+    /// Error returned if Vosk fails to decode the speech (see [`DecodingState::Failed`]).
+    #[error("vosk failed to recognize the audio")]
+    RecognitionFailed,
+
+    /// Error returned if a result of the recognizer cannot be parsed
+    /// (see [`SpeechRecognizer::try_result`]).
+    #[error("could not read the result of the recognizer")]
+    Result(#[from] ResultError),
+}
+
+/// Transcribes an audio file with a recognizer created from `model`.
+///
+/// The file can be WAV (with integer samples of any bit depth or floating point samples),
+/// FLAC, Ogg Vorbis, MP3 or AAC in an MP4 container. Its format is detected from its contents,
+/// with the extension as a hint. The audio is mixed down to a single channel according to
+/// [`TranscribeOptions::channel_mix`], resampled to the rate of the model
+/// (see [`Recognizer::with_input_rate`]), and streamed through the recognizer as it is decoded,
+/// so long files are not loaded in memory at once.
+///
+/// See [`transcribe_file_with`] to use another [`SpeechRecognizer`].
+///
+/// # Examples
+///
+/// ```no_run
+/// # use vosk::{transcribe_file, Model, TranscribeOptions};
+/// #
+/// let model = Model::new("/path/to/model").expect("Could not create a model");
+/// let transcript = transcribe_file(&model, "interview.mp3", TranscribeOptions::default())
+///     .expect("Could not transcribe the file");
+///
+/// for utterance in &transcript.utterances {
+///     println!("{}", utterance.text().unwrap_or_default());
+/// }
+/// println!("{}", transcript.final_result.text().unwrap_or_default());
+/// ```
+pub fn transcribe_file(
+    model: &Model,
+    path: impl AsRef<Path>,
+    options: TranscribeOptions,
+) -> Result<Transcript, TranscribeError> {
+    let sample_rate = Recognizer::model_rate(model);
+
+    let mut recognizer = Recognizer::new(model, sample_rate)?;
+    recognizer.set_max_alternatives(options.max_alternatives);
+    recognizer.set_words(options.words);
+
+    transcribe_file_with(recognizer, sample_rate, path, options.channel_mix)
+}
+
+/// Transcribes an audio file with any [`SpeechRecognizer`], like [`transcribe_file`].
+///
+/// * `recognizer` - The recognizer, which can also be borrowed mutably to use it afterwards.
+/// * `sample_rate` - The sample rate the recognizer expects, which the audio is resampled to.
+/// * `channel_mix` - How the channels of the file are mixed down to the single channel
+///   passed to the recognizer.
+///
+/// The audio is passed to [`SpeechRecognizer::accept_waveform_f32`], all of it before the
+/// final result is read.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use vosk::{transcribe_file_with, ChannelMix, ScriptedRecognizer};
+/// #
+/// let mut recognizer = ScriptedRecognizer::new();
+/// recognizer.push_final_result_text("hello world");
+///
+/// let transcript = transcribe_file_with(&mut recognizer, 16000.0, "hello.wav", ChannelMix::Average)
+///     .expect("Could not transcribe the file");
+/// assert_eq!(transcript.text(), "hello world");
+/// ```
+pub fn transcribe_file_with<R: SpeechRecognizer>(
+    mut recognizer: R,
+    sample_rate: f32,
+    path: impl AsRef<Path>,
+    channel_mix: ChannelMix,
+) -> Result<Transcript, TranscribeError> {
+    if !(sample_rate.is_finite() && sample_rate > 0.0) {
+        return Err(RecognizerError::InvalidSampleRate(sample_rate).into());
+    }
+
+    let path = path.as_ref();
+    let source = MediaSourceStream::new(Box::new(File::open(path)?), Default::default());
+
+    let mut hint = Hint::new();
+    if let Some(extension) = path.extension().and_then(|extension| extension.to_str()) {
+        hint.with_extension(extension);
+    }
+
+    let mut format = symphonia::default::get_probe()
+        .format(
+            &hint,
+            source,
+            &FormatOptions::default(),
+            &MetadataOptions::default(),
+        )?
+        .format;
+
+    let track = format
+        .tracks()
+        .iter()
+        .find(|track| track.codec_params.codec != CODEC_TYPE_NULL)
+        .ok_or(TranscribeError::NoAudioTrack)?;
+    let track_id = track.id;
+    let file_rate = track
+        .codec_params
+        .sample_rate
+        .ok_or(TranscribeError::UnknownSampleRate)?;
+    let mut decoder =
+        symphonia::default::get_codecs().make(&track.codec_params, &DecoderOptions::default())?;
+
+    let mut resampler =
+        (file_rate as f32 != sample_rate).then(|| Resampler::new(file_rate as f32, sample_rate));
+
+    let mut utterances = Vec::new();
+    let mut frames = 0;
+    let mut samples: Option<SampleBuffer<f32>> = None;
+    let mut mixed = Vec::new();
+
+    loop {
+        let packet = match format.next_packet() {
+            Ok(packet) => packet,
+            // The end of the file
+            Err(SymphoniaError::IoError(error)) if error.kind() == io::ErrorKind::UnexpectedEof => {
+                break
+            }
+            Err(error) => return Err(error.into()),
+        };
+        if packet.track_id() != track_id {
+            continue;
+        }
+
+        let decoded = match decoder.decode(&packet) {
+            Ok(decoded) => decoded,
+            // A corrupted packet, which can be skipped
+            Err(SymphoniaError::DecodeError(_)) => continue,
+            Err(error) => return Err(error.into()),
+        };
+
+        let spec = *decoded.spec();
+        let channels = spec.channels.count();
+        frames += decoded.frames() as u64;
+
+        // The buffer is reused, unless the packet does not fit in it
+        let samples = match &mut samples {
+            Some(samples) if samples.capacity() >= decoded.frames() * channels => samples,
+            samples => samples.insert(SampleBuffer::new(decoded.capacity() as u64, spec)),
+        };
+        samples.copy_interleaved_ref(decoded);
+
+        let channels = channels as u16;
+        let input_channels =
+            InputChannels::new(channels, channel_mix).ok_or(RecognizerError::InvalidChannels {
+                channels,
+                mix: channel_mix,
+            })?;
+
+        // Mixing and resampling are linear, so the samples stay between -1 and 1
+        let mut waveform = input_channels.mix(Waveform::Float(samples.samples()), &mut mixed)?;
+        if let Some(resampler) = &mut resampler {
+            waveform = resampler.process(waveform);
+        }
+        let Waveform::Float(mono) = waveform else {
+            unreachable!("floating point audio is mixed and resampled to floating point audio");
+        };
+
+        accept(&mut recognizer, mono, &mut utterances)?;
+    }
+
+    // The end of the audio, which the resampler holds until it knows what follows
+    if let Some(resampler) = &mut resampler {
+        let tail = resampler.flush();
+        if !tail.is_empty() {
+            accept(&mut recognizer, tail, &mut utterances)?;
+        }
+    }
+
+    Ok(Transcript {
+        utterances,
+        final_result: recognizer.try_final_result()?,
+        duration: Duration::from_secs_f64(frames as f64 / f64::from(file_rate)),
+    })
+}
+
+/// Passes `audio` to `recognizer`, adding the result to `utterances` if it ends one.
+fn accept<R: SpeechRecognizer>(
+    recognizer: &mut R,
+    audio: &[f32],
+    utterances: &mut Vec<CompleteResult<'static>>,
+) -> Result<(), TranscribeError> {
+    match recognizer.accept_waveform_f32(audio)? {
+        DecodingState::Running => {}
+        DecodingState::Finalized => utterances.push(recognizer.try_result()?),
+        DecodingState::Failed => return Err(TranscribeError::RecognitionFailed),
+    }
+    Ok(())
+}
@@ -0,0 +1,151 @@
+use super::{grammar_to_json, GrammarError, OutOfVocabulary};
+use crate::models::Model;
+
+/// The phrase that makes a grammar recognizer return `[unk]`
+/// for speech that does not match any other phrase.
+const UNKNOWN: &str = "[unk]";
+
+/// List of phrases a recognizer is limited to (see [`Recognizer::new_with_grammar`]).
+///
+/// # Examples
+///
+/// ```no_run
+/// # use vosk::{Grammar, Model, Recognizer};
+/// #
+/// let model = Model::new("/path/to/model").expect("Could not create a model");
+///
+/// let grammar = Grammar::new()
+///     .phrase("turn on the lights")
+///     .phrase("turn off the lights")
+///     .with_unknown();
+/// grammar.validate(&model).expect("The grammar has words unknown to the model");
+///
+/// let recognizer = Recognizer::new_with_grammar(&model, 16000.0, &grammar)
+///     .expect("Could not create a recognizer");
+/// ```
+///
+/// [`Recognizer::new_with_grammar`]: crate::Recognizer::new_with_grammar
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Grammar {
+    phrases: Vec<String>,
+}
+
+impl Grammar {
+    /// Creates an empty grammar.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a phrase to the grammar.
+    ///
+    /// The words in a phrase can still be recognized separately.
+    #[must_use]
+    pub fn phrase(mut self, phrase: impl Into<String>) -> Self {
+        self.phrases.push(phrase.into());
+        self
+    }
+
+    /// Adds the `[unk]` phrase to the grammar, if it was not added yet.
+    ///
+    /// With it, speech that does not match any other phrase is returned as `[unk]`.
+    /// Otherwise, the best match will be used in the result, even if it is most
+    /// likely incorrect.
+    #[must_use]
+    pub fn with_unknown(mut self) -> Self {
+        if !self.phrases.iter().any(|phrase| phrase == UNKNOWN) {
+            self.phrases.push(UNKNOWN.to_owned());
+        }
+        self
+    }
+
+    /// Returns the phrases of the grammar, in the order they were added.
+    #[must_use]
+    pub fn phrases(&self) -> &[String] {
+        &self.phrases
+    }
+
+    /// Checks that every word of every phrase can be recognized by `model`
+    /// (see [`Model::find_word`]).
+    ///
+    /// Returns [`GrammarError::OutOfVocabulary`] with the unknown words of each
+    /// phrase otherwise, as a phrase with an unknown word can never be recognized.
+    ///
+    /// [`Model::find_word`]: crate::Model::find_word
+    pub fn validate(&self, model: &Model) -> Result<(), GrammarError> {
+        let out_of_vocabulary: Vec<_> = self
+            .phrases
+            .iter()
+            .enumerate()
+            .filter(|(_, phrase)| phrase.as_str() != UNKNOWN)
+            .filter_map(|(index, phrase)| {
+                let words: Vec<_> = phrase
+                    .split_whitespace()
+                    .filter(|word| model.find_word(word).is_none())
+                    .map(String::from)
+                    .collect();
+
+                (!words.is_empty()).then(|| OutOfVocabulary {
+                    index,
+                    phrase: phrase.clone(),
+                    words,
+                })
+            })
+            .collect();
+
+        if out_of_vocabulary.is_empty() {
+            Ok(())
+        } else {
+            Err(GrammarError::OutOfVocabulary(out_of_vocabulary))
+        }
+    }
+
+    /// Encodes the grammar as the JSON array Vosk takes. See [`grammar_to_json`].
+    pub fn to_json(&self) -> Result<String, GrammarError> {
+        grammar_to_json(&self.phrases)
+    }
+}
+
+impl<S: Into<String>> FromIterator<S> for Grammar {
+    fn from_iter<I: IntoIterator<Item = S>>(iter: I) -> Self {
+        Self {
+            phrases: iter.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl<S: Into<String>> Extend<S> for Grammar {
+    fn extend<I: IntoIterator<Item = S>>(&mut self, iter: I) {
+        self.phrases.extend(iter.into_iter().map(Into::into));
+    }
+}
+
+impl From<&Grammar> for Grammar {
+    fn from(grammar: &Grammar) -> Self {
+        grammar.clone()
+    }
+}
+
+impl<T: AsRef<str>> From<&[T]> for Grammar {
+    fn from(phrases: &[T]) -> Self {
+        phrases.iter().map(AsRef::as_ref).collect()
+    }
+}
+
+impl<T: AsRef<str>, const N: usize> From<&[T; N]> for Grammar {
+    fn from(phrases: &[T; N]) -> Self {
+        phrases.iter().map(AsRef::as_ref).collect()
+    }
+}
+
+impl<T: AsRef<str>> From<&Vec<T>> for Grammar {
+    fn from(phrases: &Vec<T>) -> Self {
+        phrases.iter().map(AsRef::as_ref).collect()
+    }
+}
+
+impl<T: Into<String>> From<Vec<T>> for Grammar {
+    fn from(phrases: Vec<T>) -> Self {
+        phrases.into_iter().collect()
+    }
+}
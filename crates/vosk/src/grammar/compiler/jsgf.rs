@@ -0,0 +1,326 @@
+//! Parser for the [JSGF](https://www.w3.org/TR/jsgf/) grammar format.
+
+use super::{Expansion, Position, Rule, RuleSet};
+use crate::grammar::CompileError;
+
+/// Characters that cannot be part of an unquoted token.
+const SPECIAL_CHARACTERS: &[char] = &[
+    ';', '=', '|', '*', '+', '<', '>', '(', ')', '[', ']', '{', '}', '/', '"',
+];
+
+/// Parses the source of a JSGF grammar. Its public rules are the roots.
+pub(crate) fn parse(source: &str) -> Result<RuleSet, CompileError> {
+    Parser::new(source).parse_grammar()
+}
+
+struct Parser {
+    chars: Vec<char>,
+    index: usize,
+    position: Position,
+}
+
+impl Parser {
+    fn new(source: &str) -> Self {
+        Self {
+            chars: source.chars().collect(),
+            index: 0,
+            position: Position { line: 1, column: 1 },
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.index).copied()
+    }
+
+    fn peek_str(&self, expected: &str) -> bool {
+        expected
+            .chars()
+            .enumerate()
+            .all(|(offset, c)| self.chars.get(self.index + offset) == Some(&c))
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.index += 1;
+
+        if c == '\n' {
+            self.position.line += 1;
+            self.position.column = 1;
+        } else {
+            self.position.column += 1;
+        }
+
+        Some(c)
+    }
+
+    fn error(&self, message: impl Into<String>) -> CompileError {
+        error_at(self.position, message)
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), CompileError> {
+        self.skip_trivia()?;
+
+        match self.peek() {
+            Some(c) if c == expected => {
+                self.bump();
+                Ok(())
+            }
+            Some(c) => Err(self.error(format!("expected {expected:?}, found {c:?}"))),
+            None => Err(self.error(format!(
+                "expected {expected:?}, found the end of the grammar"
+            ))),
+        }
+    }
+
+    /// Skips whitespace and comments.
+    fn skip_trivia(&mut self) -> Result<(), CompileError> {
+        loop {
+            if self.peek().is_some_and(char::is_whitespace) {
+                self.bump();
+            } else if self.peek_str("//") {
+                while self.peek().is_some_and(|c| c != '\n') {
+                    self.bump();
+                }
+            } else if self.peek_str("/*") {
+                let start = self.position;
+                self.bump();
+                self.bump();
+
+                while !self.peek_str("*/") {
+                    if self.bump().is_none() {
+                        return Err(error_at(start, "unterminated comment"));
+                    }
+                }
+
+                self.bump();
+                self.bump();
+            } else {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Reads characters until `end` (not included) is found.
+    fn read_until(&mut self, end: char, what: &str) -> Result<String, CompileError> {
+        let start = self.position;
+        let mut text = String::new();
+
+        loop {
+            match self.bump() {
+                Some('\\') => match self.bump() {
+                    Some(c) => text.push(c),
+                    None => return Err(error_at(start, format!("unterminated {what}"))),
+                },
+                Some(c) if c == end => return Ok(text),
+                Some(c) => text.push(c),
+                None => return Err(error_at(start, format!("unterminated {what}"))),
+            }
+        }
+    }
+
+    fn read_keyword(&mut self) -> String {
+        let mut keyword = String::new();
+        while let Some(c) = self.peek().filter(char::is_ascii_alphabetic) {
+            keyword.push(c);
+            self.bump();
+        }
+        keyword
+    }
+
+    fn parse_grammar(mut self) -> Result<RuleSet, CompileError> {
+        let mut rule_set = RuleSet {
+            rules: Vec::new(),
+            roots: Vec::new(),
+        };
+
+        self.skip_trivia()?;
+        if self.peek_str("#JSGF") {
+            self.read_until(';', "header")?;
+        }
+
+        loop {
+            self.skip_trivia()?;
+
+            let Some(c) = self.peek() else {
+                return Ok(rule_set);
+            };
+
+            let position = self.position;
+            let public = if c == '<' {
+                false
+            } else {
+                match self.read_keyword().as_str() {
+                    "grammar" => {
+                        self.read_until(';', "grammar declaration")?;
+                        continue;
+                    }
+                    "import" => return Err(error_at(position, "imports are not supported")),
+                    "public" => true,
+                    "" => return Err(self.error(format!("unexpected {c:?}"))),
+                    keyword => {
+                        return Err(error_at(
+                            position,
+                            format!("unexpected keyword {keyword:?}"),
+                        ))
+                    }
+                }
+            };
+
+            let rule = self.parse_rule()?;
+            if rule_set.rules.iter().any(|other| other.name == rule.name) {
+                return Err(error_at(
+                    position,
+                    format!("rule <{}> is defined more than once", rule.name),
+                ));
+            }
+
+            if public {
+                rule_set.roots.push(rule.name.clone());
+            }
+            rule_set.rules.push(rule);
+        }
+    }
+
+    /// Parses `<name> = expansion;`.
+    fn parse_rule(&mut self) -> Result<Rule, CompileError> {
+        self.expect('<')?;
+        let name = self.read_until('>', "rule name")?;
+        self.expect('=')?;
+        let expansion = self.parse_alternatives()?;
+        self.expect(';')?;
+
+        Ok(Rule { name, expansion })
+    }
+
+    /// Parses `sequence | sequence | ...`, where each sequence may have a weight (`/10/`).
+    fn parse_alternatives(&mut self) -> Result<Expansion, CompileError> {
+        let mut alternatives = vec![self.parse_weighted_sequence()?];
+
+        loop {
+            self.skip_trivia()?;
+            if self.peek() != Some('|') {
+                break;
+            }
+
+            self.bump();
+            alternatives.push(self.parse_weighted_sequence()?);
+        }
+
+        Ok(if alternatives.len() == 1 {
+            alternatives.remove(0)
+        } else {
+            Expansion::Alternatives(alternatives)
+        })
+    }
+
+    fn parse_weighted_sequence(&mut self) -> Result<Expansion, CompileError> {
+        self.skip_trivia()?;
+
+        if self.peek() == Some('/') {
+            let position = self.position;
+            self.bump();
+            let weight = self.read_until('/', "weight")?;
+
+            // Vosk does not support weights, but they must still be valid
+            if weight.trim().parse::<f32>().is_err() {
+                return Err(error_at(position, format!("invalid weight {weight:?}")));
+            }
+        }
+
+        let mut units = Vec::new();
+        while let Some(unit) = self.parse_unit()? {
+            units.push(unit);
+        }
+
+        match units.len() {
+            0 => Err(match self.peek() {
+                Some(c) => self.error(format!("expected an expansion, found {c:?}")),
+                None => self.error("expected an expansion, found the end of the grammar"),
+            }),
+            1 => Ok(units.remove(0)),
+            _ => Ok(Expansion::Sequence(units)),
+        }
+    }
+
+    /// Parses an atom followed by any number of repetition operators (`*`, `+`) and tags.
+    fn parse_unit(&mut self) -> Result<Option<Expansion>, CompileError> {
+        self.skip_trivia()?;
+
+        let position = self.position;
+        let mut unit = match self.peek() {
+            Some('(') => {
+                self.bump();
+                let expansion = self.parse_alternatives()?;
+                self.expect(')')?;
+                expansion
+            }
+            Some('[') => {
+                self.bump();
+                let expansion = self.parse_alternatives()?;
+                self.expect(']')?;
+                Expansion::Repeat {
+                    expansion: Box::new(expansion),
+                    min: 0,
+                    max: Some(1),
+                }
+            }
+            Some('<') => {
+                self.bump();
+                let name = self.read_until('>', "rule reference")?;
+
+                match name.as_str() {
+                    "NULL" => Expansion::null(),
+                    "VOID" => Expansion::Void,
+                    "GARBAGE" => Expansion::garbage(),
+                    _ => Expansion::RuleRef { name, position },
+                }
+            }
+            Some('"') => {
+                self.bump();
+                let token = self.read_until('"', "quoted token")?;
+                Expansion::Words(token.split_whitespace().map(String::from).collect())
+            }
+            Some(c) if !c.is_whitespace() && !SPECIAL_CHARACTERS.contains(&c) => {
+                let mut token = String::new();
+                while let Some(c) = self
+                    .peek()
+                    .filter(|c| !c.is_whitespace() && !SPECIAL_CHARACTERS.contains(c))
+                {
+                    token.push(c);
+                    self.bump();
+                }
+                Expansion::Words(vec![token])
+            }
+            _ => return Ok(None),
+        };
+
+        loop {
+            self.skip_trivia()?;
+
+            unit = match self.peek() {
+                Some(operator @ ('*' | '+')) => {
+                    self.bump();
+                    Expansion::Repeat {
+                        expansion: Box::new(unit),
+                        min: u32::from(operator == '+'),
+                        max: None,
+                    }
+                }
+                Some('{') => {
+                    self.bump();
+                    let tag = self.read_until('}', "tag")?;
+                    Expansion::Sequence(vec![unit, Expansion::Tag(tag.trim().to_owned())])
+                }
+                _ => return Ok(Some(unit)),
+            };
+        }
+    }
+}
+
+fn error_at(position: Position, message: impl Into<String>) -> CompileError {
+    CompileError::Syntax {
+        line: position.line,
+        column: position.column,
+        message: message.into(),
+    }
+}
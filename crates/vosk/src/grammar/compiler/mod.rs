@@ -0,0 +1,407 @@
+mod jsgf;
+mod srgs;
+
+use super::{CompileError, Grammar};
+use std::collections::HashMap;
+
+/// Position in the source of a grammar. Lines and columns start at 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Position {
+    pub(crate) line: usize,
+    pub(crate) column: usize,
+}
+
+/// Right hand side of a rule, shared by the JSGF and SRGS parsers.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Expansion {
+    /// Words that must be said in order.
+    Words(Vec<String>),
+    /// Tag attached to the derivations that go through it. Matches no words.
+    Tag(String),
+    /// Reference to another rule of the grammar.
+    RuleRef { name: String, position: Position },
+    /// Expansions that must be matched in order.
+    Sequence(Vec<Expansion>),
+    /// Expansions of which one must be matched.
+    Alternatives(Vec<Expansion>),
+    /// Expansion matched between `min` and `max` times (unbounded if [`None`]).
+    Repeat {
+        expansion: Box<Expansion>,
+        min: u32,
+        max: Option<u32>,
+    },
+    /// Expansion that can never be matched.
+    Void,
+}
+
+impl Expansion {
+    /// Expansion that matches no words.
+    pub(crate) fn null() -> Self {
+        Self::Sequence(Vec::new())
+    }
+
+    /// Expansion that matches any speech, which Vosk represents as `[unk]`.
+    pub(crate) fn garbage() -> Self {
+        Self::Words(vec!["[unk]".to_owned()])
+    }
+}
+
+/// Rule of a grammar, as parsed from its source.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Rule {
+    pub(crate) name: String,
+    pub(crate) expansion: Expansion,
+}
+
+/// Parsed grammar, before being expanded.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct RuleSet {
+    pub(crate) rules: Vec<Rule>,
+    /// Names of the rules that are expanded into phrases.
+    pub(crate) roots: Vec<String>,
+}
+
+/// A phrase produced by a [`CompiledGrammar`], with the rule and tags it came from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompiledPhrase {
+    /// Text of the phrase, with its words separated by single spaces,
+    /// as Vosk returns it in the recognition results.
+    pub text: String,
+
+    /// Name of the root rule (a public rule in JSGF, the root rule in SRGS)
+    /// that produced the phrase.
+    pub rule: String,
+
+    /// Contents of the tags (`{...}` in JSGF, `<tag>` in SRGS) found along the way,
+    /// in order.
+    pub tags: Vec<String>,
+}
+
+/// Flat list of phrases a JSGF or SRGS grammar expands to, which can be passed to
+/// [`Recognizer::new_with_grammar`].
+///
+/// It also allows mapping the recognized text back to the rule and tags that produced it,
+/// with [`interpret`](Self::interpret).
+///
+/// # Examples
+///
+/// ```no_run
+/// # use vosk::{CompiledGrammar, Model, Recognizer};
+/// #
+/// let grammar = CompiledGrammar::from_jsgf(
+///     "#JSGF V1.0;
+///     grammar lights;
+///     public <command> = turn (on {on} | off {off}) [the] lights;",
+/// )
+/// .expect("Invalid grammar");
+///
+/// let model = Model::new("/path/to/model").expect("Could not create a model");
+/// let mut recognizer = Recognizer::new_with_grammar(&model, 16000.0, &grammar)
+///     .expect("Could not create a recognizer");
+///
+/// # let samples: [i16; 0] = [];
+/// recognizer.accept_waveform(&samples).unwrap();
+/// let result = recognizer.final_result().single().unwrap();
+///
+/// if let Some(phrase) = grammar.interpret(&result.text) {
+///     println!("matched rule {} with tags {:?}", phrase.rule, phrase.tags);
+/// }
+/// ```
+///
+/// [`Recognizer::new_with_grammar`]: crate::Recognizer::new_with_grammar
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompiledGrammar {
+    phrases: Vec<CompiledPhrase>,
+    index: HashMap<String, usize>,
+}
+
+impl CompiledGrammar {
+    /// Compiles a JSGF grammar with the default [`GrammarCompiler`] options.
+    pub fn from_jsgf(source: &str) -> Result<Self, CompileError> {
+        GrammarCompiler::new().compile_jsgf(source)
+    }
+
+    /// Compiles an SRGS (XML form) grammar with the default [`GrammarCompiler`] options.
+    pub fn from_srgs(source: &str) -> Result<Self, CompileError> {
+        GrammarCompiler::new().compile_srgs(source)
+    }
+
+    /// Returns the phrases of the grammar. Phrases that can be produced in more than one way
+    /// only appear once, with the first rule and tags that produced them.
+    #[must_use]
+    pub fn phrases(&self) -> &[CompiledPhrase] {
+        &self.phrases
+    }
+
+    /// Returns the phrase that matches the `text` returned by a recognizer, if any.
+    ///
+    /// Differences in whitespace are ignored.
+    #[must_use]
+    pub fn interpret(&self, text: &str) -> Option<&CompiledPhrase> {
+        let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+        self.index.get(&text).map(|&index| &self.phrases[index])
+    }
+
+    /// Returns the flat list of phrases as a [`Grammar`].
+    #[must_use]
+    pub fn to_grammar(&self) -> Grammar {
+        self.phrases
+            .iter()
+            .map(|phrase| phrase.text.as_str())
+            .collect()
+    }
+}
+
+impl From<&CompiledGrammar> for Grammar {
+    fn from(grammar: &CompiledGrammar) -> Self {
+        grammar.to_grammar()
+    }
+}
+
+/// Compiles JSGF and SRGS grammars into the flat phrase lists Vosk takes.
+///
+/// Alternatives, optional parts, repetitions and references to other rules are expanded
+/// into every phrase they can produce. Weights are ignored, as Vosk does not support them.
+/// Imports and external rule references are not supported.
+///
+/// Since the number of phrases grows quickly with the size of the grammar, compiling fails
+/// if it exceeds [`max_phrases`](Self::max_phrases).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GrammarCompiler {
+    max_phrases: usize,
+    max_repeat: u32,
+}
+
+impl Default for GrammarCompiler {
+    fn default() -> Self {
+        Self {
+            max_phrases: 10_000,
+            max_repeat: 3,
+        }
+    }
+}
+
+impl GrammarCompiler {
+    /// Creates a compiler with the default options.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the maximum number of phrases a grammar may expand to.
+    ///
+    /// Default: 10000.
+    #[must_use]
+    pub fn max_phrases(mut self, max_phrases: usize) -> Self {
+        self.max_phrases = max_phrases;
+        self
+    }
+
+    /// Sets how many times unbounded repetitions (`*` and `+` in JSGF, `repeat="m-"` in SRGS)
+    /// are expanded at most.
+    ///
+    /// Default: 3.
+    #[must_use]
+    pub fn max_repeat(mut self, max_repeat: u32) -> Self {
+        self.max_repeat = max_repeat;
+        self
+    }
+
+    /// Compiles a grammar in the [JSGF](https://www.w3.org/TR/jsgf/) format.
+    ///
+    /// Every public rule is expanded.
+    pub fn compile_jsgf(&self, source: &str) -> Result<CompiledGrammar, CompileError> {
+        self.compile(jsgf::parse(source)?)
+    }
+
+    /// Compiles a grammar in the XML form of the [SRGS](https://www.w3.org/TR/speech-grammar/)
+    /// format.
+    ///
+    /// The rule named by the `root` attribute is expanded or, if there is none,
+    /// every public rule.
+    pub fn compile_srgs(&self, source: &str) -> Result<CompiledGrammar, CompileError> {
+        self.compile(srgs::parse(source)?)
+    }
+
+    fn compile(&self, rule_set: RuleSet) -> Result<CompiledGrammar, CompileError> {
+        if rule_set.roots.is_empty() {
+            return Err(CompileError::NoRootRule);
+        }
+
+        let mut expander = Expander {
+            rules: rule_set
+                .rules
+                .iter()
+                .map(|rule| (rule.name.as_str(), &rule.expansion))
+                .collect(),
+            options: self,
+            stack: Vec::new(),
+            cache: HashMap::new(),
+        };
+
+        let mut grammar = CompiledGrammar {
+            phrases: Vec::new(),
+            index: HashMap::new(),
+        };
+
+        for root in &rule_set.roots {
+            for derivation in expander.expand_rule(root, None)? {
+                if derivation.words.is_empty() {
+                    continue;
+                }
+
+                let text = derivation.words.join(" ");
+                if grammar.index.contains_key(&text) {
+                    continue;
+                }
+
+                if grammar.phrases.len() == self.max_phrases {
+                    return Err(CompileError::TooManyPhrases(self.max_phrases));
+                }
+
+                grammar.index.insert(text.clone(), grammar.phrases.len());
+                grammar.phrases.push(CompiledPhrase {
+                    text,
+                    rule: root.clone(),
+                    tags: derivation.tags,
+                });
+            }
+        }
+
+        Ok(grammar)
+    }
+}
+
+/// One way of matching an expansion.
+#[derive(Debug, Clone, Default)]
+struct Derivation {
+    words: Vec<String>,
+    tags: Vec<String>,
+}
+
+impl Derivation {
+    fn concat(&self, other: &Self) -> Self {
+        Self {
+            words: [self.words.as_slice(), &other.words].concat(),
+            tags: [self.tags.as_slice(), &other.tags].concat(),
+        }
+    }
+}
+
+struct Expander<'a> {
+    rules: HashMap<&'a str, &'a Expansion>,
+    options: &'a GrammarCompiler,
+    /// Rules being expanded, to detect recursion.
+    stack: Vec<&'a str>,
+    cache: HashMap<&'a str, Vec<Derivation>>,
+}
+
+impl<'a> Expander<'a> {
+    fn check_limit(&self, len: Option<usize>) -> Result<usize, CompileError> {
+        len.filter(|&len| len <= self.options.max_phrases)
+            .ok_or(CompileError::TooManyPhrases(self.options.max_phrases))
+    }
+
+    fn expand_rule(
+        &mut self,
+        name: &'a str,
+        position: Option<Position>,
+    ) -> Result<Vec<Derivation>, CompileError> {
+        if let Some(derivations) = self.cache.get(name) {
+            return Ok(derivations.clone());
+        }
+
+        if self.stack.contains(&name) {
+            return Err(CompileError::RecursiveRule(name.to_owned()));
+        }
+
+        let expansion = *self.rules.get(name).ok_or_else(|| {
+            let Position { line, column } = position.unwrap_or(Position { line: 1, column: 1 });
+            CompileError::UndefinedRule {
+                rule: name.to_owned(),
+                line,
+                column,
+            }
+        })?;
+
+        self.stack.push(name);
+        let derivations = self.expand(expansion)?;
+        self.stack.pop();
+
+        self.cache.insert(name, derivations.clone());
+        Ok(derivations)
+    }
+
+    fn expand(&mut self, expansion: &'a Expansion) -> Result<Vec<Derivation>, CompileError> {
+        Ok(match expansion {
+            Expansion::Words(words) => vec![Derivation {
+                words: words.clone(),
+                tags: Vec::new(),
+            }],
+            Expansion::Tag(tag) => vec![Derivation {
+                words: Vec::new(),
+                tags: vec![tag.clone()],
+            }],
+            Expansion::RuleRef { name, position } => self.expand_rule(name, Some(*position))?,
+            Expansion::Sequence(expansions) => {
+                let mut derivations = vec![Derivation::default()];
+                for expansion in expansions {
+                    let next = self.expand(expansion)?;
+                    derivations = self.product(&derivations, &next)?;
+                }
+                derivations
+            }
+            Expansion::Alternatives(expansions) => {
+                let mut derivations = Vec::new();
+                for expansion in expansions {
+                    let next = self.expand(expansion)?;
+                    self.check_limit(derivations.len().checked_add(next.len()))?;
+                    derivations.extend(next);
+                }
+                derivations
+            }
+            Expansion::Repeat {
+                expansion,
+                min,
+                max,
+            } => {
+                let once = self.expand(expansion)?;
+                let (mut min, mut max) = (*min, max.unwrap_or(self.options.max_repeat.max(*min)));
+
+                // Repeating an expansion without words never adds new phrases, so do it
+                // once at most, however large the bounds are
+                if once.iter().all(|derivation| derivation.words.is_empty()) {
+                    (min, max) = (min.min(1), max.min(1));
+                }
+
+                let mut repeated = vec![Derivation::default()];
+                for _ in 0..min {
+                    repeated = self.product(&repeated, &once)?;
+                }
+
+                let mut derivations = repeated.clone();
+                for _ in min..max {
+                    repeated = self.product(&repeated, &once)?;
+                    self.check_limit(derivations.len().checked_add(repeated.len()))?;
+                    derivations.extend(repeated.iter().cloned());
+                }
+                derivations
+            }
+            Expansion::Void => Vec::new(),
+        })
+    }
+
+    /// Returns every derivation of `first` followed by every derivation of `second`.
+    fn product(
+        &self,
+        first: &[Derivation],
+        second: &[Derivation],
+    ) -> Result<Vec<Derivation>, CompileError> {
+        self.check_limit(first.len().checked_mul(second.len()))?;
+
+        Ok(first
+            .iter()
+            .flat_map(|a| second.iter().map(move |b| a.concat(b)))
+            .collect())
+    }
+}
@@ -0,0 +1,210 @@
+//! Parser for the XML form of the [SRGS](https://www.w3.org/TR/speech-grammar/) grammar format.
+
+use super::{Expansion, Position, Rule, RuleSet};
+use crate::grammar::CompileError;
+use roxmltree::{Document, Node};
+
+/// Parses the source of an SRGS grammar. The root rule is the root or, if there is none,
+/// its public rules.
+pub(crate) fn parse(source: &str) -> Result<RuleSet, CompileError> {
+    let document = Document::parse(source).map_err(|error| {
+        let position = error.pos();
+        CompileError::Syntax {
+            line: position.row as usize,
+            column: position.col as usize,
+            message: error.to_string(),
+        }
+    })?;
+
+    let grammar = document.root_element();
+    if grammar.tag_name().name() != "grammar" {
+        return Err(error_at(
+            grammar,
+            format!("expected <grammar>, found <{}>", grammar.tag_name().name()),
+        ));
+    }
+
+    let mut rule_set = RuleSet {
+        rules: Vec::new(),
+        roots: Vec::new(),
+    };
+    let mut public_rules = Vec::new();
+
+    for node in grammar.children().filter(Node::is_element) {
+        match node.tag_name().name() {
+            "rule" => {
+                let name = node
+                    .attribute("id")
+                    .ok_or_else(|| error_at(node, "<rule> without an id"))?
+                    .to_owned();
+
+                if rule_set.rules.iter().any(|rule| rule.name == name) {
+                    return Err(error_at(
+                        node,
+                        format!("rule {name:?} is defined more than once"),
+                    ));
+                }
+
+                if node.attribute("scope") == Some("public") {
+                    public_rules.push(name.clone());
+                }
+
+                rule_set.rules.push(Rule {
+                    name,
+                    expansion: parse_children(node)?,
+                });
+            }
+            "meta" | "metadata" | "lexicon" | "tag" => {}
+            other => return Err(error_at(node, format!("unexpected element <{other}>"))),
+        }
+    }
+
+    rule_set.roots = match grammar.attribute("root") {
+        Some(root) => vec![root.to_owned()],
+        None => public_rules,
+    };
+
+    Ok(rule_set)
+}
+
+/// Parses the contents of `node` as a sequence.
+fn parse_children(node: Node) -> Result<Expansion, CompileError> {
+    let mut sequence = Vec::new();
+
+    for child in node.children() {
+        if child.is_text() {
+            let words: Vec<_> = child
+                .text()
+                .unwrap_or_default()
+                .split_whitespace()
+                .map(String::from)
+                .collect();
+
+            if !words.is_empty() {
+                sequence.push(Expansion::Words(words));
+            }
+
+            continue;
+        }
+
+        if !child.is_element() {
+            continue;
+        }
+
+        sequence.push(match child.tag_name().name() {
+            "item" => parse_item(child)?,
+            "one-of" => Expansion::Alternatives(
+                child
+                    .children()
+                    .filter(Node::is_element)
+                    .map(|item| match item.tag_name().name() {
+                        "item" => parse_item(item),
+                        other => Err(error_at(
+                            item,
+                            format!("expected <item> inside <one-of>, found <{other}>"),
+                        )),
+                    })
+                    .collect::<Result<_, _>>()?,
+            ),
+            "ruleref" => parse_ruleref(child)?,
+            "token" => Expansion::Words(
+                text_content(child)
+                    .split_whitespace()
+                    .map(String::from)
+                    .collect(),
+            ),
+            "tag" => Expansion::Tag(text_content(child).trim().to_owned()),
+            "example" | "meta" | "metadata" | "lexicon" => continue,
+            other => return Err(error_at(child, format!("unexpected element <{other}>"))),
+        });
+    }
+
+    Ok(if sequence.len() == 1 {
+        sequence.remove(0)
+    } else {
+        Expansion::Sequence(sequence)
+    })
+}
+
+/// Parses an `<item>`, which may be repeated (`repeat="n"`, `"m-n"` or `"m-"`).
+/// Weights and repeat probabilities are ignored.
+fn parse_item(node: Node) -> Result<Expansion, CompileError> {
+    let expansion = parse_children(node)?;
+
+    let Some(repeat) = node.attribute("repeat") else {
+        return Ok(expansion);
+    };
+
+    let invalid_repeat = || error_at(node, format!("invalid repeat {repeat:?}"));
+    let parse_count = |count: &str| count.trim().parse::<u32>().map_err(|_| invalid_repeat());
+
+    let (min, max) = match repeat.split_once('-') {
+        Some((min, "")) => (parse_count(min)?, None),
+        Some((min, max)) => (parse_count(min)?, Some(parse_count(max)?)),
+        None => {
+            let count = parse_count(repeat)?;
+            (count, Some(count))
+        }
+    };
+
+    if max.is_some_and(|max| max < min) {
+        return Err(invalid_repeat());
+    }
+
+    Ok(Expansion::Repeat {
+        expansion: Box::new(expansion),
+        min,
+        max,
+    })
+}
+
+/// Parses a `<ruleref>` to a local rule (`uri="#name"`) or a special rule.
+fn parse_ruleref(node: Node) -> Result<Expansion, CompileError> {
+    if let Some(special) = node.attribute("special") {
+        return match special {
+            "NULL" => Ok(Expansion::null()),
+            "VOID" => Ok(Expansion::Void),
+            "GARBAGE" => Ok(Expansion::garbage()),
+            other => Err(error_at(node, format!("unknown special rule {other:?}"))),
+        };
+    }
+
+    let uri = node
+        .attribute("uri")
+        .ok_or_else(|| error_at(node, "<ruleref> without an uri or special attribute"))?;
+
+    match uri.strip_prefix('#') {
+        Some(name) => Ok(Expansion::RuleRef {
+            name: name.to_owned(),
+            position: position_of(node),
+        }),
+        None => Err(error_at(
+            node,
+            format!("references to external grammars are not supported ({uri:?})"),
+        )),
+    }
+}
+
+fn text_content(node: Node) -> String {
+    node.descendants()
+        .filter(Node::is_text)
+        .filter_map(|text| text.text())
+        .collect()
+}
+
+fn position_of(node: Node) -> Position {
+    let position = node.document().text_pos_at(node.range().start);
+    Position {
+        line: position.row as usize,
+        column: position.col as usize,
+    }
+}
+
+fn error_at(node: Node, message: impl Into<String>) -> CompileError {
+    let Position { line, column } = position_of(node);
+    CompileError::Syntax {
+        line,
+        column,
+        message: message.into(),
+    }
+}
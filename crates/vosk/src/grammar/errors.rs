@@ -0,0 +1,98 @@
+use std::fmt;
+use thiserror::Error;
+
+/// Possible errors when passing a grammar to Vosk.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum GrammarError {
+    /// Error returned if a phrase contains a control character (such as `'\0'` or `'\n'`).
+    /// Vosk cannot read them back from the JSON grammar, so they would silently change it.
+    #[error("phrase {index} ({phrase:?}) contains the control character {character:?}")]
+    ControlCharacter {
+        /// Position of the phrase in the grammar.
+        index: usize,
+        /// The phrase itself.
+        phrase: String,
+        /// The first control character found in the phrase.
+        character: char,
+    },
+
+    /// Error returned by [`Grammar::validate`] if some phrases contain words
+    /// the model cannot recognize.
+    ///
+    /// [`Grammar::validate`]: crate::Grammar::validate
+    #[error(
+        "the grammar contains words unknown to the model: {}",
+        .0.iter().map(ToString::to_string).collect::<Vec<_>>().join("; ")
+    )]
+    OutOfVocabulary(Vec<OutOfVocabulary>),
+}
+
+/// A phrase with words that the model cannot recognize.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutOfVocabulary {
+    /// Position of the phrase in the grammar.
+    pub index: usize,
+    /// The phrase itself.
+    pub phrase: String,
+    /// The words of the phrase that are not in the vocabulary of the model, in order.
+    pub words: Vec<String>,
+}
+
+impl fmt::Display for OutOfVocabulary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} in phrase {} ({:?})",
+            self.words.join(", "),
+            self.index,
+            self.phrase
+        )
+    }
+}
+
+/// Possible errors when compiling a JSGF or SRGS grammar (see [`GrammarCompiler`]).
+///
+/// Lines and columns start at 1.
+///
+/// [`GrammarCompiler`]: crate::GrammarCompiler
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum CompileError {
+    /// Error returned if the source of the grammar is malformed.
+    #[error("{line}:{column}: {message}")]
+    Syntax {
+        /// Line where the problem was found.
+        line: usize,
+        /// Column where the problem was found.
+        column: usize,
+        /// Description of the problem.
+        message: String,
+    },
+
+    /// Error returned if a rule references a rule that is not defined in the grammar.
+    #[error("{line}:{column}: reference to undefined rule {rule:?}")]
+    UndefinedRule {
+        /// Name of the undefined rule.
+        rule: String,
+        /// Line of the reference.
+        line: usize,
+        /// Column of the reference.
+        column: usize,
+    },
+
+    /// Error returned if a rule references itself, directly or through other rules,
+    /// so it would expand to an infinite number of phrases.
+    #[error("rule {0:?} is recursive, so it cannot be expanded into phrases")]
+    RecursiveRule(String),
+
+    /// Error returned if the grammar does not have any rule to expand: a public rule in
+    /// JSGF, or the root rule (or a public rule, if there is no root) in SRGS.
+    #[error("the grammar does not have any root or public rule")]
+    NoRootRule,
+
+    /// Error returned if the grammar expands to more phrases than the configured limit
+    /// (see [`GrammarCompiler::max_phrases`]).
+    ///
+    /// [`GrammarCompiler::max_phrases`]: crate::GrammarCompiler::max_phrases
+    #[error("the grammar expands to more than {0} phrases")]
+    TooManyPhrases(usize),
+}
@@ -0,0 +1,141 @@
+use super::Grammar;
+use crate::recognition::{Recognizer, RecognizerError};
+
+/// Set of phrases that changes over time, such as the commands that are valid on the current
+/// screen of an application, and that is pushed to a [`Recognizer`] when it changes.
+///
+/// Phrases are kept in the order they were added, without duplicates.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use vosk::{GrammarManager, Model, Recognizer};
+/// #
+/// let model = Model::new("/path/to/model").expect("Could not create a model");
+/// let mut recognizer = Recognizer::new(&model, 16000.0).expect("Could not create a recognizer");
+///
+/// let mut commands = GrammarManager::new().with_unknown();
+/// commands.replace(["open settings", "go back"]);
+/// commands.apply(&mut recognizer).expect("Could not set the grammar");
+///
+/// // The settings screen was opened
+/// commands.remove("open settings");
+/// commands.add("toggle dark mode");
+/// commands.apply(&mut recognizer).expect("Could not set the grammar");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GrammarManager {
+    phrases: Vec<String>,
+    unknown: bool,
+    changed: bool,
+}
+
+impl GrammarManager {
+    /// Creates a manager with no phrases.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Makes the grammar always include the `[unk]` phrase (see [`Grammar::with_unknown`]).
+    #[must_use]
+    pub fn with_unknown(mut self) -> Self {
+        self.unknown = true;
+        self.changed = true;
+        self
+    }
+
+    /// Adds a phrase. Returns `false` if it was already there.
+    pub fn add(&mut self, phrase: impl Into<String>) -> bool {
+        let phrase = phrase.into();
+        if self.contains(&phrase) {
+            return false;
+        }
+
+        self.phrases.push(phrase);
+        self.changed = true;
+        true
+    }
+
+    /// Removes a phrase. Returns `false` if it was not there.
+    pub fn remove(&mut self, phrase: &str) -> bool {
+        let Some(index) = self.phrases.iter().position(|other| other == phrase) else {
+            return false;
+        };
+
+        self.phrases.remove(index);
+        self.changed = true;
+        true
+    }
+
+    /// Replaces all the phrases at once.
+    pub fn replace<S: Into<String>>(&mut self, phrases: impl IntoIterator<Item = S>) {
+        self.phrases.clear();
+        for phrase in phrases {
+            self.add(phrase);
+        }
+        self.changed = true;
+    }
+
+    /// Removes all the phrases.
+    pub fn clear(&mut self) {
+        self.replace(std::iter::empty::<String>());
+    }
+
+    /// Returns whether `phrase` is part of the set.
+    #[must_use]
+    pub fn contains(&self, phrase: &str) -> bool {
+        self.phrases.iter().any(|other| other == phrase)
+    }
+
+    /// Returns the phrases, in the order they were added.
+    #[must_use]
+    pub fn phrases(&self) -> &[String] {
+        &self.phrases
+    }
+
+    /// Returns whether the phrases changed since the last successful [`apply`](Self::apply).
+    #[must_use]
+    pub fn has_changes(&self) -> bool {
+        self.changed
+    }
+
+    /// Returns the current phrases as a [`Grammar`].
+    #[must_use]
+    pub fn grammar(&self) -> Grammar {
+        let grammar: Grammar = self.phrases.iter().map(String::as_str).collect();
+
+        if self.unknown {
+            grammar.with_unknown()
+        } else {
+            grammar
+        }
+    }
+
+    /// Pushes the current phrases to `recognizer` with [`Recognizer::set_grammar`], if they
+    /// changed since the last successful call. Returns whether they were pushed.
+    ///
+    /// Changes are tracked for the manager, not for each recognizer, so to keep several
+    /// recognizers up to date, call [`Recognizer::set_grammar`] with [`grammar`](Self::grammar)
+    /// on each of them instead.
+    ///
+    /// Note that if there are no phrases (and `[unk]` was not added with
+    /// [`with_unknown`](Self::with_unknown)), the recognizer is not limited to any phrase.
+    pub fn apply(&mut self, recognizer: &mut Recognizer) -> Result<bool, RecognizerError> {
+        if !self.changed {
+            return Ok(false);
+        }
+
+        recognizer.set_grammar(&self.grammar())?;
+        self.changed = false;
+        Ok(true)
+    }
+}
+
+impl<S: Into<String>> FromIterator<S> for GrammarManager {
+    fn from_iter<I: IntoIterator<Item = S>>(iter: I) -> Self {
+        let mut manager = Self::new();
+        manager.replace(iter);
+        manager
+    }
+}
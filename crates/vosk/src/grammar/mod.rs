@@ -0,0 +1,50 @@
+mod builder;
+mod compiler;
+mod errors;
+mod manager;
+
+pub use builder::Grammar;
+pub use compiler::{CompiledGrammar, CompiledPhrase, GrammarCompiler};
+pub use errors::*;
+pub use manager::GrammarManager;
+
+use std::ffi::CString;
+
+/// Encodes a list of phrases as the JSON array of strings that Vosk takes as a grammar,
+/// for example `["one two three four five", "[unk]"]`.
+///
+/// Quotes, backslashes and non-ASCII text are escaped or encoded as needed, so each phrase
+/// reaches Vosk exactly as it was given. Phrases with control characters are rejected,
+/// as Vosk cannot decode them.
+///
+/// # Examples
+///
+/// ```
+/// # use vosk::grammar_to_json;
+/// #
+/// let json = grammar_to_json(&["say \"hello\"", "[unk]"]).unwrap();
+/// assert_eq!(json, r#"["say \"hello\"","[unk]"]"#);
+/// ```
+pub fn grammar_to_json(phrases: &[impl AsRef<str>]) -> Result<String, GrammarError> {
+    let phrases: Vec<&str> = phrases.iter().map(AsRef::as_ref).collect();
+
+    for (index, phrase) in phrases.iter().enumerate() {
+        if let Some(character) = phrase.chars().find(|c| c.is_control()) {
+            return Err(GrammarError::ControlCharacter {
+                index,
+                phrase: (*phrase).to_owned(),
+                character,
+            });
+        }
+    }
+
+    // UNWRAP: Serializing a list of strings cannot fail
+    Ok(serde_json::to_string(&phrases).unwrap())
+}
+
+/// Encodes a list of phrases like [`grammar_to_json`] and converts it to a C string.
+pub(crate) fn grammar_to_c_string(phrases: &[impl AsRef<str>]) -> Result<CString, GrammarError> {
+    // UNWRAP: Control characters (including NUL) were rejected, and JSON
+    // escape sequences never contain a NUL byte
+    grammar_to_json(phrases).map(|json| CString::new(json).unwrap())
+}
@@ -9,13 +9,38 @@
 //! * Feel audio to the recognizer with [`Recognizer::accept_waveform`]
 //! * Get the processed result with [`Recognizer::result`],
 //!   [`Recognizer::partial_result`] or [`Recognizer::final_result`]
+//!
+//! A [`Transcriber`] does the last two steps for a whole stream of audio, yielding an [`Event`]
+//! for each chunk.
+//!
+//! Code that should also work with other recognizers, such as a [`ScriptedRecognizer`] in tests,
+//! can be generic over [`SpeechRecognizer`] instead.
+//!
+//! With the `audio` feature, `transcribe_file` decodes and transcribes an audio file in one call
+//! (or `transcribe_file_with`, with any [`SpeechRecognizer`]).
+//!
+//! With the `dynamic` feature, the Vosk library is not linked at build time,
+//! and must be loaded with `load_library` before anything else is used.
+
+// Provides the Vosk functions, which are otherwise unused
+#[cfg(feature = "mock")]
+extern crate vosk_mock;
 
+#[cfg(feature = "audio")]
+mod audio;
 #[cfg(feature = "batch")]
 mod gpu;
+mod grammar;
+#[cfg(feature = "dynamic")]
+mod library;
 mod log;
 mod models;
 mod recognition;
 
-pub use crate::{log::*, models::*, recognition::*};
+pub use crate::{grammar::*, log::*, models::*, recognition::*};
+#[cfg(feature = "audio")]
+pub use audio::*;
 #[cfg(feature = "batch")]
 pub use gpu::*;
+#[cfg(feature = "dynamic")]
+pub use library::*;
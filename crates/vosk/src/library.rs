@@ -0,0 +1,30 @@
+use std::path::Path;
+
+pub use vosk_sys::LoadError;
+
+/// Loads the Vosk library from `path` (for example `libvosk.so`, `libvosk.dylib` or
+/// `libvosk.dll`) instead of linking it at build time.
+///
+/// It must be called once, before anything else in this crate is used, which panics otherwise.
+/// The functions of the Vosk API are resolved right away, so a library that lacks
+/// some of them is rejected with [`LoadError::MissingSymbols`]. Older versions of Vosk
+/// can still be loaded without grammars, endpointer settings, text processing
+/// and batch recognition, which panic when used.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use vosk::{load_library, Model};
+/// #
+/// load_library("/opt/vosk/libvosk.so").expect("Could not load Vosk");
+/// let model = Model::new("/path/to/model").expect("Could not create a model");
+/// ```
+pub fn load_library(path: impl AsRef<Path>) -> Result<(), LoadError> {
+    vosk_sys::load(path)
+}
+
+/// Returns whether the Vosk library was loaded with [`load_library`].
+#[must_use]
+pub fn is_library_loaded() -> bool {
+    vosk_sys::is_loaded()
+}
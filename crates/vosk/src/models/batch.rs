@@ -1,4 +1,5 @@
-use std::{ffi::CString, ptr::NonNull};
+use super::{paths, ModelError};
+use std::{path::Path, ptr::NonNull};
 use vosk_sys::*;
 
 /// The same as [`Model`], but uses
@@ -8,16 +9,26 @@ use vosk_sys::*;
 pub struct BatchModel(pub(crate) NonNull<VoskBatchModel>);
 
 impl BatchModel {
-    /// Loads model data from the file and returns the model object, or [`None`]
-    /// if a problem occured.
+    /// Loads model data from the file and returns the model object.
     ///
-    /// * `model_path` - the path to the model directory.
-    #[must_use]
-    pub fn new(model_path: impl Into<String>) -> Option<Self> {
-        let model_path_c = CString::new(model_path.into()).ok()?;
+    /// The directory is checked like in [`Model::new`] before calling Vosk.
+    ///
+    /// * `model_path` - the path to the model directory. Relative paths are resolved
+    ///   against [`models_dir`] if it was set.
+    ///
+    /// [`models_dir`]: crate::models_dir
+    ///
+    /// [`Model::new`]: super::sequential::Model::new
+    pub fn new(model_path: impl AsRef<Path>) -> Result<Self, ModelError> {
+        let path = paths::resolve(model_path.as_ref());
+        let model_path_c = paths::path_to_c_string(&path)?;
+        paths::check_model_dir(&path)?;
+
         let model_ptr = unsafe { vosk_batch_model_new(model_path_c.as_ptr()) };
 
-        Some(Self(NonNull::new(model_ptr)?))
+        NonNull::new(model_ptr)
+            .map(Self)
+            .ok_or(ModelError::LoadFailed(path))
     }
 
     /// Waits for inferencing to finish
@@ -0,0 +1,62 @@
+use std::{io, path::PathBuf};
+use thiserror::Error;
+
+/// Possible errors that model (and [`TextProcessor`](crate::TextProcessor)) constructors might return.
+#[derive(Error, Debug)]
+pub enum ModelError {
+    /// Error returned if the model path contains a NUL byte,
+    /// so it cannot be passed to Vosk.
+    #[error("the model path {0:?} contains a NUL byte")]
+    NulInPath(PathBuf),
+
+    /// Error returned if the model path is not valid unicode on a platform where
+    /// Vosk only accepts UTF-8 paths (every platform except Unix).
+    #[error("the model path {0:?} is not valid unicode")]
+    NonUnicodePath(PathBuf),
+
+    /// Error returned if the model path does not point to a directory.
+    #[error("the model directory {0:?} does not exist")]
+    DirectoryNotFound(PathBuf),
+
+    /// Error returned if a path that should point to a file (such as the FSTs of a
+    /// [`TextProcessor`](crate::TextProcessor)) does not.
+    #[error("the file {0:?} does not exist")]
+    FileNotFound(PathBuf),
+
+    /// Error returned if the model directory lacks files that Vosk needs to load it.
+    #[error("the model directory {path:?} is missing {}", missing.join(", "))]
+    MissingFiles {
+        /// Path to the model directory.
+        path: PathBuf,
+        /// Description of each missing piece, relative to [`path`](Self::MissingFiles::path).
+        missing: Vec<String>,
+    },
+
+    /// Error returned if a file of the model could not be read.
+    #[error("could not read {path:?}")]
+    Io {
+        /// Path to the file.
+        path: PathBuf,
+        /// Underlying error.
+        #[source]
+        source: io::Error,
+    },
+
+    /// Error returned if a configuration file or the word list of the model could not be parsed.
+    #[error("invalid contents in {path:?}, line {line}: {message}")]
+    InvalidFile {
+        /// Path to the file.
+        path: PathBuf,
+        /// Line (starting at 1) where the problem was found.
+        line: usize,
+        /// Description of the problem.
+        message: String,
+    },
+
+    /// Error returned if the directory looked valid but Vosk could not load the model.
+    /// The reason will have been logged by Vosk (see [`set_log_level`]).
+    ///
+    /// [`set_log_level`]: crate::set_log_level
+    #[error("vosk failed to load the model at {0:?}")]
+    LoadFailed(PathBuf),
+}
@@ -0,0 +1,234 @@
+use super::{
+    paths::{self, read_to_string, Layout},
+    ModelError,
+};
+use std::{path::Path, str::FromStr};
+
+/// Kind of decoding graph a model was built with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GraphKind {
+    /// Lookahead graph (`HCLr.fst` and `Gr.fst`), composed at runtime.
+    /// Supports grammars (see [`Recognizer::new_with_grammar`]).
+    ///
+    /// [`Recognizer::new_with_grammar`]: crate::Recognizer::new_with_grammar
+    Lookahead,
+
+    /// Precompiled static graph (`HCLG.fst`). Does not support grammars.
+    Static,
+}
+
+/// Options of one of the endpointing rules, which decide when an utterance is over
+/// (see [`DecodingState::Finalized`]).
+///
+/// Each field is [`None`] if the model does not override it, in which case
+/// the Kaldi default is used.
+///
+/// [`DecodingState::Finalized`]: crate::DecodingState::Finalized
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct EndpointRule {
+    /// Whether the rule only applies if something other than silence was decoded.
+    pub must_contain_nonsilence: Option<bool>,
+
+    /// Seconds of trailing silence needed for the rule to apply.
+    pub min_trailing_silence: Option<f32>,
+
+    /// Maximum relative cost of the final state for the rule to apply.
+    pub max_relative_cost: Option<f32>,
+
+    /// Seconds the utterance must last for the rule to apply.
+    pub min_utterance_length: Option<f32>,
+}
+
+/// Endpointing configuration of a model, read from `conf/model.conf`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EndpointConfig {
+    /// Colon-separated list of the phones considered silence.
+    pub silence_phones: Option<String>,
+
+    /// Kaldi's five endpointing rules, in order (`rule1` to `rule5`).
+    /// An utterance ends as soon as any of them applies.
+    pub rules: [EndpointRule; 5],
+}
+
+/// Information about a model, read from the files in its directory.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use vosk::{Model, Recognizer};
+/// #
+/// let model = Model::new("/path/to/model").expect("Could not create a model");
+/// let info = model.info().expect("Could not read the model information");
+///
+/// let sample_rate = 44100.0;
+/// if info.sample_frequency.is_some_and(|model_rate| model_rate != sample_rate) {
+///     eprintln!("the audio will be resampled, which can hurt accuracy");
+/// }
+///
+/// let recognizer = Recognizer::new(&model, sample_rate).expect("Could not create a recognizer");
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModelInfo {
+    /// Sample frequency the model was trained with, from `conf/mfcc.conf`.
+    ///
+    /// Audio at other rates is resampled by Vosk, so for best accuracy this is
+    /// the rate that should be passed to [`Recognizer::new`].
+    ///
+    /// [`Recognizer::new`]: crate::Recognizer::new
+    pub sample_frequency: Option<f32>,
+
+    /// Endpointing rules of the model.
+    pub endpoint: EndpointConfig,
+
+    /// Kind of decoding graph of the model.
+    pub graph: GraphKind,
+
+    /// Whether the model contains an RNN language model (`rnnlm/`) to rescore results.
+    pub has_rnnlm: bool,
+
+    /// Whether the model contains a large language model (`rescore/`) to rescore results.
+    pub has_rescore: bool,
+
+    /// Number of entries in the word symbol table (`graph/words.txt`),
+    /// or [`None`] if the model does not have one.
+    pub vocabulary_size: Option<usize>,
+}
+
+impl ModelInfo {
+    /// Reads the information of the model in `model_path` without loading it.
+    ///
+    /// * `model_path` - the path to the model directory. Relative paths are resolved
+    ///   against [`models_dir`] if it was set.
+    ///
+    /// [`models_dir`]: crate::models_dir
+    pub fn from_dir(model_path: impl AsRef<Path>) -> Result<Self, ModelError> {
+        let path = paths::resolve(model_path.as_ref());
+        let graph = paths::check_model_dir(&path)?;
+
+        let layout = Layout::detect(&path);
+        let conf_dir = path.join(layout.conf);
+        let graph_dir = path.join(layout.graph);
+
+        let mfcc_conf = conf_dir.join("mfcc.conf");
+        let mut sample_frequency = None;
+        for (line, name, value) in read_conf(&mfcc_conf)? {
+            if name == "sample-frequency" {
+                sample_frequency = Some(parse_value(&mfcc_conf, line, &value)?);
+            }
+        }
+
+        let mut endpoint = EndpointConfig::default();
+        let model_conf = conf_dir.join("model.conf");
+        if model_conf.is_file() {
+            for (line, name, value) in read_conf(&model_conf)? {
+                let Some(option) = name.strip_prefix("endpoint.") else {
+                    continue;
+                };
+
+                if option == "silence-phones" {
+                    endpoint.silence_phones = Some(value);
+                    continue;
+                }
+
+                let Some((index, rule_option)) = option
+                    .strip_prefix("rule")
+                    .and_then(|rule| rule.split_once('.'))
+                    .and_then(|(index, rule_option)| {
+                        Some((index.parse::<usize>().ok()?, rule_option))
+                    })
+                    .filter(|(index, _)| (1..=5).contains(index))
+                else {
+                    continue;
+                };
+
+                let rule = &mut endpoint.rules[index - 1];
+                match rule_option {
+                    "must-contain-nonsilence" => {
+                        rule.must_contain_nonsilence = Some(parse_bool(&model_conf, line, &value)?);
+                    }
+                    "min-trailing-silence" => {
+                        rule.min_trailing_silence = Some(parse_value(&model_conf, line, &value)?);
+                    }
+                    "max-relative-cost" => {
+                        rule.max_relative_cost = Some(parse_value(&model_conf, line, &value)?);
+                    }
+                    "min-utterance-length" => {
+                        rule.min_utterance_length = Some(parse_value(&model_conf, line, &value)?);
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        let words = graph_dir.join("words.txt");
+        let vocabulary_size = if words.is_file() {
+            Some(
+                read_to_string(&words)?
+                    .lines()
+                    .filter(|line| !line.trim().is_empty())
+                    .count(),
+            )
+        } else {
+            None
+        };
+
+        Ok(Self {
+            sample_frequency,
+            endpoint,
+            graph,
+            has_rnnlm: path.join("rnnlm/final.raw").is_file(),
+            has_rescore: path.join("rescore/G.carpa").is_file()
+                || path.join("rescore/G.fst").is_file(),
+            vocabulary_size,
+        })
+    }
+}
+
+/// Reads a Kaldi configuration file, returning the line number, name
+/// and value of each `--name=value` option in it.
+fn read_conf(path: &Path) -> Result<Vec<(usize, String, String)>, ModelError> {
+    let mut options = Vec::new();
+
+    for (index, line) in read_to_string(path)?.lines().enumerate() {
+        let line_number = index + 1;
+        let line = line.split('#').next().unwrap_or_default();
+
+        for option in line.split_whitespace() {
+            let option = option
+                .strip_prefix("--")
+                .ok_or_else(|| ModelError::InvalidFile {
+                    path: path.to_owned(),
+                    line: line_number,
+                    message: format!("expected an option starting with \"--\", found {option:?}"),
+                })?;
+
+            // Boolean options can be written without a value
+            let (name, value) = option.split_once('=').unwrap_or((option, "true"));
+            options.push((line_number, name.to_owned(), value.to_owned()));
+        }
+    }
+
+    Ok(options)
+}
+
+fn parse_value<T: FromStr>(path: &Path, line: usize, value: &str) -> Result<T, ModelError> {
+    value.parse().map_err(|_| ModelError::InvalidFile {
+        path: path.to_owned(),
+        line,
+        message: format!("invalid value {value:?}"),
+    })
+}
+
+/// Parses a boolean the way Kaldi does, which also accepts `t`/`f` and `1`/`0`
+/// in any case.
+fn parse_bool(path: &Path, line: usize, value: &str) -> Result<bool, ModelError> {
+    match value.to_ascii_lowercase().as_str() {
+        "true" | "t" | "1" => Ok(true),
+        "false" | "f" | "0" => Ok(false),
+        _ => Err(ModelError::InvalidFile {
+            path: path.to_owned(),
+            line,
+            message: format!("invalid boolean {value:?}"),
+        }),
+    }
+}
@@ -1,7 +1,19 @@
 #[cfg(feature = "batch")]
 mod batch;
+mod errors;
+mod info;
+pub(crate) mod paths;
 mod sequential;
+mod shared;
+mod text_processor;
+mod vocabulary;
 
 #[cfg(feature = "batch")]
 pub use batch::BatchModel;
+pub use errors::*;
+pub use info::{EndpointConfig, EndpointRule, GraphKind, ModelInfo};
+pub use paths::{models_dir, set_models_dir};
 pub use sequential::{Model, SpeakerModel};
+pub use shared::{ModelRef, SharedModel, SharedSpeakerModel};
+pub use text_processor::TextProcessor;
+pub use vocabulary::Vocabulary;
@@ -0,0 +1,179 @@
+//! Handling of model paths before they are passed to Vosk, so problems
+//! can be reported precisely instead of as a NULL model.
+
+use super::{GraphKind, ModelError};
+use std::{
+    ffi::CString,
+    fs,
+    path::{Path, PathBuf},
+    sync::RwLock,
+};
+
+static MODELS_DIR: RwLock<Option<PathBuf>> = RwLock::new(None);
+
+/// Set the directory that relative model paths are resolved against.
+///
+/// Passing [`None`] restores the default, which is resolving them against the
+/// current working directory. Absolute paths are never affected.
+///
+/// This is global state of the process: the directory is shared by every thread, and changing
+/// it affects the models all of them load afterwards. It is best set once, at startup.
+///
+/// Default: [`None`].
+pub fn set_models_dir(dir: Option<PathBuf>) {
+    // UNWRAP: The lock is never held across code that can panic
+    *MODELS_DIR.write().unwrap() = dir;
+}
+
+/// Returns the directory set with [`set_models_dir`], if any.
+#[must_use]
+pub fn models_dir() -> Option<PathBuf> {
+    // UNWRAP: The lock is never held across code that can panic
+    MODELS_DIR.read().unwrap().clone()
+}
+
+/// Resolves `path` against the directory set with [`set_models_dir`], if it is relative.
+pub(crate) fn resolve(path: &Path) -> PathBuf {
+    match models_dir() {
+        Some(dir) if path.is_relative() => dir.join(path),
+        _ => path.to_owned(),
+    }
+}
+
+/// Converts `path` to a C string that can be passed to Vosk.
+///
+/// On Unix the raw bytes of the path are used, so paths that are not valid UTF-8 work too.
+#[cfg(unix)]
+pub(crate) fn path_to_c_string(path: &Path) -> Result<CString, ModelError> {
+    use std::os::unix::ffi::OsStrExt;
+
+    CString::new(path.as_os_str().as_bytes()).map_err(|_| ModelError::NulInPath(path.to_owned()))
+}
+
+/// Converts `path` to a C string that can be passed to Vosk.
+#[cfg(not(unix))]
+pub(crate) fn path_to_c_string(path: &Path) -> Result<CString, ModelError> {
+    let path_str = path
+        .to_str()
+        .ok_or_else(|| ModelError::NonUnicodePath(path.to_owned()))?;
+
+    CString::new(path_str).map_err(|_| ModelError::NulInPath(path.to_owned()))
+}
+
+/// Location of the pieces of a model inside its directory, relative to it.
+///
+/// Both the current layout (`am/`, `conf/` and `graph/` subdirectories) and the legacy one
+/// (everything at the root of the directory) are supported, like Vosk does.
+pub(crate) struct Layout {
+    pub(crate) am: &'static str,
+    pub(crate) conf: &'static str,
+    pub(crate) graph: &'static str,
+}
+
+impl Layout {
+    /// Returns the layout used by the model in `path`.
+    pub(crate) fn detect(path: &Path) -> Self {
+        if path.join("final.mdl").is_file() {
+            Self {
+                am: "",
+                conf: "",
+                graph: "",
+            }
+        } else {
+            Self {
+                am: "am/",
+                conf: "conf/",
+                graph: "graph/",
+            }
+        }
+    }
+}
+
+/// Checks that `path` is a directory containing an acoustic model,
+/// its feature configuration and a decoding graph, and returns the kind of the graph.
+pub(crate) fn check_model_dir(path: &Path) -> Result<GraphKind, ModelError> {
+    check_dir_exists(path)?;
+
+    let Layout { am, conf, graph } = Layout::detect(path);
+    let mut missing = Vec::new();
+
+    for file in [format!("{am}final.mdl"), format!("{conf}mfcc.conf")] {
+        if !path.join(&file).is_file() {
+            missing.push(file);
+        }
+    }
+
+    let kind = graph_kind(path);
+    if kind.is_none() {
+        missing.push(format!(
+            "a graph ({graph}HCLG.fst, or {graph}HCLr.fst and {graph}Gr.fst)"
+        ));
+    }
+
+    match kind {
+        Some(kind) if missing.is_empty() => Ok(kind),
+        _ => Err(ModelError::MissingFiles {
+            path: path.to_owned(),
+            missing,
+        }),
+    }
+}
+
+/// Checks that `path` is a directory containing a speaker identification model.
+pub(crate) fn check_speaker_model_dir(path: &Path) -> Result<(), ModelError> {
+    check_dir_exists(path)?;
+
+    let missing: Vec<_> = ["mfcc.conf", "final.ext.raw", "mean.vec", "transform.mat"]
+        .into_iter()
+        .filter(|file| !path.join(file).is_file())
+        .map(String::from)
+        .collect();
+
+    if missing.is_empty() {
+        Ok(())
+    } else {
+        Err(ModelError::MissingFiles {
+            path: path.to_owned(),
+            missing,
+        })
+    }
+}
+
+/// Returns the kind of decoding graph of the model in `path`, or [`None`] if it has none.
+pub(crate) fn graph_kind(path: &Path) -> Option<GraphKind> {
+    let graph_dir = path.join(Layout::detect(path).graph);
+
+    if graph_dir.join("HCLr.fst").is_file() && graph_dir.join("Gr.fst").is_file() {
+        Some(GraphKind::Lookahead)
+    } else if graph_dir.join("HCLG.fst").is_file() {
+        Some(GraphKind::Static)
+    } else {
+        None
+    }
+}
+
+/// Checks that `path` is a directory.
+pub(crate) fn check_dir_exists(path: &Path) -> Result<(), ModelError> {
+    if path.is_dir() {
+        Ok(())
+    } else {
+        Err(ModelError::DirectoryNotFound(path.to_owned()))
+    }
+}
+
+/// Checks that `path` is a file.
+pub(crate) fn check_file_exists(path: &Path) -> Result<(), ModelError> {
+    if path.is_file() {
+        Ok(())
+    } else {
+        Err(ModelError::FileNotFound(path.to_owned()))
+    }
+}
+
+/// Reads a file of a model to a string.
+pub(crate) fn read_to_string(path: &Path) -> Result<String, ModelError> {
+    fs::read_to_string(path).map_err(|source| ModelError::Io {
+        path: path.to_owned(),
+        source,
+    })
+}
@@ -1,4 +1,9 @@
-use std::{ffi::CString, ptr::NonNull};
+use super::{paths, GraphKind, ModelError, ModelInfo, Vocabulary};
+use std::{
+    ffi::CString,
+    path::{Path, PathBuf},
+    ptr::NonNull,
+};
 use vosk_sys::*;
 
 // SAFETY:
@@ -8,19 +13,60 @@ use vosk_sys::*;
 // https://github.com/alphacep/vosk-api/blob/a7bc5a22d411e22bebf4df1cc5554b473c7456d4/src/vosk_api.h
 
 /// Model that stores all the data required for recognition.
-pub struct Model(pub(crate) NonNull<VoskModel>);
+pub struct Model {
+    pub(crate) ptr: NonNull<VoskModel>,
+    path: PathBuf,
+    graph: GraphKind,
+}
 
 impl Model {
-    /// Loads model data from the file and returns the model object, or [`None`]
-    /// if a problem occured.
+    /// Loads model data from the file and returns the model object.
     ///
-    /// * `model_path` - the path to the model directory.
-    #[must_use]
-    pub fn new(model_path: impl Into<String>) -> Option<Self> {
-        let model_path_c = CString::new(model_path.into()).ok()?;
+    /// The directory is checked for the acoustic model (`am/final.mdl`), its
+    /// configuration (`conf/mfcc.conf`) and a decoding graph (`graph/`) before calling Vosk,
+    /// so a [`ModelError`] can tell exactly what is wrong with it.
+    ///
+    /// * `model_path` - the path to the model directory. Relative paths are resolved
+    ///   against [`models_dir`] if it was set.
+    ///
+    /// [`models_dir`]: crate::models_dir
+    pub fn new(model_path: impl AsRef<Path>) -> Result<Self, ModelError> {
+        let path = paths::resolve(model_path.as_ref());
+        let model_path_c = paths::path_to_c_string(&path)?;
+        let graph = paths::check_model_dir(&path)?;
+
         let model_ptr = unsafe { vosk_model_new(model_path_c.as_ptr()) };
 
-        Some(Self(NonNull::new(model_ptr)?))
+        match NonNull::new(model_ptr) {
+            Some(ptr) => Ok(Self { ptr, path, graph }),
+            None => Err(ModelError::LoadFailed(path)),
+        }
+    }
+
+    /// Path to the directory the model was loaded from.
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Kind of decoding graph of the model, detected when it was loaded.
+    ///
+    /// Only [`GraphKind::Lookahead`] models support grammars.
+    #[must_use]
+    pub fn graph(&self) -> GraphKind {
+        self.graph
+    }
+
+    /// Reads information about the model from the files in its directory.
+    /// See [`ModelInfo::from_dir`].
+    pub fn info(&self) -> Result<ModelInfo, ModelError> {
+        ModelInfo::from_dir(&self.path)
+    }
+
+    /// Reads the words the model can recognize from its word symbol table.
+    /// See [`Vocabulary::from_dir`].
+    pub fn vocabulary(&self) -> Result<Vocabulary, ModelError> {
+        Vocabulary::from_dir(&self.path)
     }
 
     /// Check if a word can be recognized by the model.
@@ -28,11 +74,13 @@ impl Model {
     /// If it is not, this returns None.
     ///
     /// Word symbol `0` is for `<epsilon>`.
+    ///
+    /// To check many words at once, use [`vocabulary`](Self::vocabulary).
     #[must_use]
-    pub fn find_word(&mut self, word: &str) -> Option<u32> {
+    pub fn find_word(&self, word: &str) -> Option<u32> {
         let word_c = CString::new(word).ok()?;
 
-        let symbol = unsafe { vosk_model_find_word(self.0.as_ptr(), word_c.as_ptr()) };
+        let symbol = unsafe { vosk_model_find_word(self.ptr.as_ptr(), word_c.as_ptr()) };
 
         if symbol == -1 {
             None
@@ -45,7 +93,7 @@ impl Model {
 
 impl Drop for Model {
     fn drop(&mut self) {
-        unsafe { vosk_model_free(self.0.as_ptr()) }
+        unsafe { vosk_model_free(self.ptr.as_ptr()) }
     }
 }
 
@@ -56,16 +104,25 @@ unsafe impl Sync for Model {}
 pub struct SpeakerModel(pub(crate) NonNull<VoskSpkModel>);
 
 impl SpeakerModel {
-    /// Loads speaker model data from the file and returns the model
-    /// object, or [`None`] if a problem occured.
+    /// Loads speaker model data from the file and returns the model object.
     ///
-    /// * `model_path` - the path to the model in the filesystem.
-    #[must_use]
-    pub fn new(model_path: impl Into<String>) -> Option<Self> {
-        let model_path_c = CString::new(model_path.into()).ok()?;
+    /// The directory is checked for the files Vosk needs (`mfcc.conf`, `final.ext.raw`,
+    /// `mean.vec` and `transform.mat`) before calling it.
+    ///
+    /// * `model_path` - the path to the model in the filesystem. Relative paths are resolved
+    ///   against [`models_dir`] if it was set.
+    ///
+    /// [`models_dir`]: crate::models_dir
+    pub fn new(model_path: impl AsRef<Path>) -> Result<Self, ModelError> {
+        let path = paths::resolve(model_path.as_ref());
+        let model_path_c = paths::path_to_c_string(&path)?;
+        paths::check_speaker_model_dir(&path)?;
+
         let model_ptr = unsafe { vosk_spk_model_new(model_path_c.as_ptr()) };
 
-        Some(Self(NonNull::new(model_ptr)?))
+        NonNull::new(model_ptr)
+            .map(Self)
+            .ok_or(ModelError::LoadFailed(path))
     }
 }
 
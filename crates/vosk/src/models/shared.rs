@@ -0,0 +1,170 @@
+use super::{
+    sequential::{Model, SpeakerModel},
+    ModelError, TextProcessor,
+};
+use std::{ops::Deref, path::Path, sync::Arc};
+
+/// Reference-counted handle to a [`Model`].
+///
+/// Cloning it is cheap and all the clones point to the same model data. A [`Recognizer`]
+/// created from a [`SharedModel`] (rather than from a reference to it) owns a strong reference,
+/// so it is `'static` and can be moved to other threads or tasks.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use vosk::{Recognizer, SharedModel};
+/// #
+/// let model = SharedModel::new("/path/to/model").expect("Could not create a model");
+///
+/// let handles: Vec<_> = (0..4)
+///     .map(|_| {
+///         let mut recognizer =
+///             Recognizer::new(model.clone(), 16000.0).expect("Could not create a recognizer");
+///
+///         std::thread::spawn(move || {
+///             recognizer.accept_waveform(&[0; 100]).unwrap();
+///             println!("{:#?}", recognizer.final_result());
+///         })
+///     })
+///     .collect();
+///
+/// for handle in handles {
+///     handle.join().unwrap();
+/// }
+/// ```
+///
+/// [`Recognizer`]: crate::Recognizer
+#[derive(Clone)]
+pub struct SharedModel(Arc<Model>);
+
+impl SharedModel {
+    /// Loads model data from the file and returns a shared handle to it.
+    /// See [`Model::new`].
+    ///
+    /// * `model_path` - the path to the model directory. Relative paths are resolved
+    ///   against [`models_dir`] if it was set.
+    ///
+    /// [`models_dir`]: crate::models_dir
+    pub fn new(model_path: impl AsRef<Path>) -> Result<Self, ModelError> {
+        Ok(Self(Arc::new(Model::new(model_path)?)))
+    }
+}
+
+impl From<Model> for SharedModel {
+    fn from(model: Model) -> Self {
+        Self(Arc::new(model))
+    }
+}
+
+impl Deref for SharedModel {
+    type Target = Model;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+/// Reference-counted handle to a [`SpeakerModel`].
+///
+/// The speaker counterpart of [`SharedModel`].
+#[derive(Clone)]
+pub struct SharedSpeakerModel(Arc<SpeakerModel>);
+
+impl SharedSpeakerModel {
+    /// Loads speaker model data from the file and returns a shared handle to it.
+    /// See [`SpeakerModel::new`].
+    ///
+    /// * `model_path` - the path to the model in the filesystem. Relative paths are resolved
+    ///   against [`models_dir`] if it was set.
+    ///
+    /// [`models_dir`]: crate::models_dir
+    pub fn new(model_path: impl AsRef<Path>) -> Result<Self, ModelError> {
+        Ok(Self(Arc::new(SpeakerModel::new(model_path)?)))
+    }
+}
+
+impl From<SpeakerModel> for SharedSpeakerModel {
+    fn from(model: SpeakerModel) -> Self {
+        Self(Arc::new(model))
+    }
+}
+
+impl Deref for SharedSpeakerModel {
+    type Target = SpeakerModel;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+/// A model kept alive by a recognizer, either borrowed for `'m` or
+/// shared through a strong reference.
+///
+/// It is rarely named directly: recognizer constructors take `impl Into<ModelRef>`,
+/// so they accept a reference to a model (or to a shared handle), or an owned shared handle.
+pub enum ModelRef<'m, T> {
+    /// The model is borrowed and must outlive the recognizer.
+    Borrowed(&'m T),
+    /// The recognizer holds a strong reference to the model.
+    Shared(Arc<T>),
+}
+
+impl<T> Deref for ModelRef<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        match self {
+            Self::Borrowed(model) => model,
+            Self::Shared(model) => model,
+        }
+    }
+}
+
+impl<'m> From<&'m Model> for ModelRef<'m, Model> {
+    fn from(model: &'m Model) -> Self {
+        Self::Borrowed(model)
+    }
+}
+
+impl<'m> From<&'m SharedModel> for ModelRef<'m, Model> {
+    fn from(model: &'m SharedModel) -> Self {
+        Self::Borrowed(&model.0)
+    }
+}
+
+impl From<SharedModel> for ModelRef<'static, Model> {
+    fn from(model: SharedModel) -> Self {
+        Self::Shared(model.0)
+    }
+}
+
+impl<'m> From<&'m SpeakerModel> for ModelRef<'m, SpeakerModel> {
+    fn from(model: &'m SpeakerModel) -> Self {
+        Self::Borrowed(model)
+    }
+}
+
+impl<'m> From<&'m SharedSpeakerModel> for ModelRef<'m, SpeakerModel> {
+    fn from(model: &'m SharedSpeakerModel) -> Self {
+        Self::Borrowed(&model.0)
+    }
+}
+
+impl From<SharedSpeakerModel> for ModelRef<'static, SpeakerModel> {
+    fn from(model: SharedSpeakerModel) -> Self {
+        Self::Shared(model.0)
+    }
+}
+
+impl<'m> From<&'m TextProcessor> for ModelRef<'m, TextProcessor> {
+    fn from(processor: &'m TextProcessor) -> Self {
+        Self::Borrowed(processor)
+    }
+}
+
+impl From<Arc<TextProcessor>> for ModelRef<'static, TextProcessor> {
+    fn from(processor: Arc<TextProcessor>) -> Self {
+        Self::Shared(processor)
+    }
+}
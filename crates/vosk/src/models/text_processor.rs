@@ -0,0 +1,114 @@
+use super::{paths, ModelError};
+use std::{
+    ffi::{c_char, CStr, CString},
+    path::Path,
+    ptr::NonNull,
+    sync::Mutex,
+};
+use vosk_sys::*;
+
+/// Inverse text normalization, which turns spoken forms into written ones,
+/// like "twenty five dollars" into "$25".
+///
+/// It is loaded from a pair of FST files, the tagger and the verbalizer, which some
+/// models ship in their `itn/` directory. It can be used on its own with [`itn`], or set on a
+/// [`Recognizer`] with [`Recognizer::set_text_processor`] so the text of its results is converted.
+///
+/// Vosk returns the converted text in a string allocated with the C runtime of libvosk, which
+/// this crate frees with the C runtime it is linked against. They are the same on Linux and macOS,
+/// but on Windows, libvosk must be built against the same C runtime as the program.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use vosk::TextProcessor;
+/// #
+/// let processor = TextProcessor::new(
+///     "/path/to/model/itn/en_itn_tagger.fst",
+///     "/path/to/model/itn/en_itn_verbalizer.fst",
+/// )
+/// .expect("Could not create a text processor");
+///
+/// assert_eq!(processor.itn("twenty five dollars"), "$25");
+/// ```
+///
+/// [`itn`]: Self::itn
+/// [`Recognizer`]: crate::Recognizer
+/// [`Recognizer::set_text_processor`]: crate::Recognizer::set_text_processor
+pub struct TextProcessor {
+    ptr: NonNull<VoskTextProcessor>,
+    // Vosk does not document whether processing text concurrently is safe
+    lock: Mutex<()>,
+}
+
+impl TextProcessor {
+    /// Loads the tagger and verbalizer FSTs and returns the text processor.
+    ///
+    /// Relative paths are resolved against [`models_dir`] if it was set.
+    ///
+    /// [`models_dir`]: crate::models_dir
+    pub fn new(
+        tagger_path: impl AsRef<Path>,
+        verbalizer_path: impl AsRef<Path>,
+    ) -> Result<Self, ModelError> {
+        let tagger_path = paths::resolve(tagger_path.as_ref());
+        let verbalizer_path = paths::resolve(verbalizer_path.as_ref());
+
+        let tagger_path_c = paths::path_to_c_string(&tagger_path)?;
+        let verbalizer_path_c = paths::path_to_c_string(&verbalizer_path)?;
+        paths::check_file_exists(&tagger_path)?;
+        paths::check_file_exists(&verbalizer_path)?;
+
+        let processor_ptr =
+            unsafe { vosk_text_processor_new(tagger_path_c.as_ptr(), verbalizer_path_c.as_ptr()) };
+
+        Ok(Self {
+            ptr: NonNull::new(processor_ptr).ok_or(ModelError::LoadFailed(tagger_path))?,
+            lock: Mutex::new(()),
+        })
+    }
+
+    /// Converts the spoken forms in `text` into written ones.
+    #[must_use]
+    pub fn itn(&self, text: &str) -> String {
+        // Vosk takes NUL-terminated strings, so NUL characters are kept as they are
+        // and the text between them is converted separately
+        text.split('\0')
+            .map(|part| self.itn_part(part))
+            .collect::<Vec<_>>()
+            .join("\0")
+    }
+
+    fn itn_part(&self, text: &str) -> String {
+        // UNWRAP: text was split at NUL characters
+        let text_c = CString::new(text).unwrap();
+
+        // UNWRAP: The lock is never held across code that can panic
+        let _guard = self.lock.lock().unwrap();
+        let result_ptr: *mut c_char =
+            unsafe { vosk_text_processor_itn(self.ptr.as_ptr(), text_c.as_ptr()) };
+
+        if result_ptr.is_null() {
+            return text.to_owned();
+        }
+
+        let result = unsafe { CStr::from_ptr(result_ptr) }
+            .to_string_lossy()
+            .into_owned();
+        // Vosk allocates the string with strdup and has no function to free it
+        unsafe { libc::free(result_ptr.cast()) };
+
+        result
+    }
+}
+
+// SAFETY: TextProcessor does not depend on the thread it was created in, and
+// processing text goes through a lock, so it is Send and Sync
+unsafe impl Send for TextProcessor {}
+unsafe impl Sync for TextProcessor {}
+
+impl Drop for TextProcessor {
+    fn drop(&mut self) {
+        unsafe { vosk_text_processor_free(self.ptr.as_ptr()) }
+    }
+}
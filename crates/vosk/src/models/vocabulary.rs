@@ -0,0 +1,196 @@
+use super::{
+    paths::{self, read_to_string, Layout},
+    ModelError,
+};
+use std::{collections::BTreeMap, ops::Bound, path::Path};
+
+/// Words a model can recognize, read from its word symbol table (`graph/words.txt`).
+///
+/// Unlike [`Model::find_word`], which asks Vosk for one word at a time, this allows
+/// checking many words at once, searching by prefix and suggesting corrections for
+/// words the model does not know.
+///
+/// The table also contains the special symbols of the graph, such as `<eps>` (id `0`)
+/// or the disambiguation symbols (`#0`, ...).
+///
+/// # Examples
+///
+/// ```no_run
+/// # use vosk::Model;
+/// #
+/// let model = Model::new("/path/to/model").expect("Could not create a model");
+/// let vocabulary = model.vocabulary().expect("Could not read the vocabulary");
+///
+/// for word in vocabulary.missing(&["turn", "on", "the", "lihgts"]) {
+///     println!("{word:?} is not known, did you mean {:?}?", vocabulary.suggestions(word, 2));
+/// }
+/// ```
+///
+/// [`Model::find_word`]: crate::Model::find_word
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Vocabulary {
+    words: BTreeMap<String, u32>,
+}
+
+impl Vocabulary {
+    /// Reads the vocabulary of the model in `model_path` without loading it.
+    ///
+    /// * `model_path` - the path to the model directory. Relative paths are resolved
+    ///   against [`models_dir`] if it was set.
+    ///
+    /// [`models_dir`]: crate::models_dir
+    pub fn from_dir(model_path: impl AsRef<Path>) -> Result<Self, ModelError> {
+        let path = paths::resolve(model_path.as_ref());
+        paths::check_dir_exists(&path)?;
+
+        let words_path = Path::new(Layout::detect(&path).graph).join("words.txt");
+
+        if !path.join(&words_path).is_file() {
+            return Err(ModelError::MissingFiles {
+                missing: vec![words_path.to_string_lossy().into_owned()],
+                path,
+            });
+        }
+
+        Self::from_file(&path.join(words_path))
+    }
+
+    /// Parses a word symbol table, with a `word id` pair per line.
+    fn from_file(path: &Path) -> Result<Self, ModelError> {
+        let mut words = BTreeMap::new();
+
+        for (index, line) in read_to_string(path)?.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let invalid_line = || ModelError::InvalidFile {
+                path: path.to_owned(),
+                line: index + 1,
+                message: format!("expected a word and its id, found {line:?}"),
+            };
+
+            let mut fields = line.split_whitespace();
+            let (Some(word), Some(id), None) = (fields.next(), fields.next(), fields.next()) else {
+                return Err(invalid_line());
+            };
+
+            words.insert(word.to_owned(), id.parse().map_err(|_| invalid_line())?);
+        }
+
+        Ok(Self { words })
+    }
+
+    /// Returns the number of words in the vocabulary.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    /// Returns `true` if the vocabulary has no words.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    /// Returns an iterator over the words and their ids, in alphabetical order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, u32)> {
+        self.words.iter().map(|(word, &id)| (word.as_str(), id))
+    }
+
+    /// Returns the id of `word` if it is in the vocabulary, like [`Model::find_word`].
+    ///
+    /// [`Model::find_word`]: crate::Model::find_word
+    #[must_use]
+    pub fn get(&self, word: &str) -> Option<u32> {
+        self.words.get(word).copied()
+    }
+
+    /// Returns `true` if `word` is in the vocabulary.
+    #[must_use]
+    pub fn contains(&self, word: &str) -> bool {
+        self.words.contains_key(word)
+    }
+
+    /// Returns `true` if all `words` are in the vocabulary.
+    #[must_use]
+    pub fn contains_all(&self, words: &[&str]) -> bool {
+        words.iter().all(|word| self.contains(word))
+    }
+
+    /// Returns the `words` that are not in the vocabulary, in the same order.
+    #[must_use]
+    pub fn missing<'w>(&self, words: &[&'w str]) -> Vec<&'w str> {
+        words
+            .iter()
+            .filter(|word| !self.contains(word))
+            .copied()
+            .collect()
+    }
+
+    /// Returns an iterator over the words starting with `prefix` and their ids,
+    /// in alphabetical order.
+    pub fn with_prefix<'a>(&'a self, prefix: &'a str) -> impl Iterator<Item = (&'a str, u32)> {
+        self.words
+            .range::<str, _>((Bound::Included(prefix), Bound::Unbounded))
+            .take_while(move |(word, _)| word.starts_with(prefix))
+            .map(|(word, &id)| (word.as_str(), id))
+    }
+
+    /// Returns the words at an edit distance of at most `max_distance` from `word`,
+    /// closest first, as "did you mean" suggestions for a word missing from the vocabulary.
+    ///
+    /// The distance is the number of single character insertions, deletions or substitutions
+    /// needed to turn one word into the other. Special symbols (`<eps>`, `#0`, ...) are
+    /// never suggested.
+    #[must_use]
+    pub fn suggestions(&self, word: &str, max_distance: usize) -> Vec<&str> {
+        let word: Vec<char> = word.chars().collect();
+
+        let mut suggestions: Vec<_> = self
+            .words
+            .keys()
+            .filter(|candidate| !is_special_symbol(candidate))
+            .filter_map(|candidate| {
+                let distance = edit_distance(&word, candidate, max_distance)?;
+                Some((distance, candidate.as_str()))
+            })
+            .collect();
+
+        suggestions.sort_unstable();
+        suggestions.into_iter().map(|(_, word)| word).collect()
+    }
+}
+
+fn is_special_symbol(word: &str) -> bool {
+    (word.starts_with('<') && word.ends_with('>')) || word.starts_with('#')
+}
+
+/// Levenshtein distance between `a` and `b`, or [`None`] if it is greater than `max`.
+fn edit_distance(a: &[char], b: &str, max: usize) -> Option<usize> {
+    let b: Vec<char> = b.chars().collect();
+    if a.len().abs_diff(b.len()) > max {
+        return None;
+    }
+
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+
+    for (i, a_char) in a.iter().enumerate() {
+        current[0] = i + 1;
+
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_char != b_char);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+
+        // Distances never decrease from one row to the next, so give up early
+        if current.iter().all(|&distance| distance > max) {
+            return None;
+        }
+
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    Some(previous[b.len()]).filter(|&distance| distance <= max)
+}
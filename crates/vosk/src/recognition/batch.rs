@@ -1,18 +1,31 @@
 use super::{
-    errors::AcceptWaveformError,
-    results::{result_from_json_c_str, Word},
+    errors::{AcceptWaveformError, RecognizerError, ResultError},
+    results::{complete_result_from_c_str, expect_result, CompleteResult},
 };
 use crate::models::BatchModel;
 use vosk_sys::*;
 
-use std::ptr::NonNull;
+use std::{marker::PhantomData, ptr::NonNull};
 
 /// The main object which processes data using GPU inferencing.
 /// Takes audio as input and returns decoded information as words, confidences, times, and other metadata.
-pub struct BatchRecognizer(std::ptr::NonNull<VoskBatchRecognizer>);
+///
+/// A recognizer borrows the [`BatchModel`] it was built from, so the model cannot
+/// be dropped while it is still in use:
+///
+/// ```compile_fail,E0505
+/// # use vosk::{BatchModel, BatchRecognizer};
+/// let model = BatchModel::new("/path/to/model").unwrap();
+/// let mut recognizer = BatchRecognizer::new(&model, 16000.0).unwrap();
+/// drop(model);
+/// recognizer.accept_waveform(&[0; 100]).unwrap();
+/// ```
+///
+/// [`BatchModel`]: crate::BatchModel
+pub struct BatchRecognizer<'m>(NonNull<VoskBatchRecognizer>, PhantomData<&'m BatchModel>);
 
-impl BatchRecognizer {
-    /// Creates the recognizer object. Returns [`None`] if a problem occured.
+impl<'m> BatchRecognizer<'m> {
+    /// Creates the recognizer object.
     ///
     /// The recognizers process the speech and return text using shared model data.
     ///
@@ -23,13 +36,19 @@ impl BatchRecognizer {
     ///   Make sure this rate matches the audio content, it is a common issue causing accuracy problems.
     ///
     /// [`BatchModel`]: crate::BatchModel
-    #[must_use]
-    pub fn new(model: &BatchModel, sample_rate: f32) -> Option<Self> {
+    pub fn new(model: &'m BatchModel, sample_rate: f32) -> Result<Self, RecognizerError> {
         let recognizer_ptr = unsafe { vosk_batch_recognizer_new(model.0.as_ptr(), sample_rate) };
-        Some(Self(NonNull::new(recognizer_ptr)?))
+
+        NonNull::new(recognizer_ptr)
+            .map(|ptr| Self(ptr, PhantomData))
+            .ok_or(RecognizerError::CreationFailed)
     }
 
     /// Enables or disables Natural Language Semantics Markup Language (NLSML) in the output.
+    ///
+    /// When enabled, [`front_result`] returns a [`CompleteResult::Nlsml`].
+    ///
+    /// [`front_result`]: Self::front_result
     pub fn set_nlsml(&mut self, enable: bool) {
         unsafe { vosk_batch_recognizer_set_nlsml(self.0.as_ptr(), i32::from(enable)) }
     }
@@ -57,8 +76,23 @@ impl BatchRecognizer {
     }
 
     /// Gets the front of the result queue.
-    pub fn front_result(&mut self) -> Word {
-        unsafe { result_from_json_c_str(vosk_batch_recognizer_front_result(self.0.as_ptr())) }
+    ///
+    /// # Panics
+    ///
+    /// Panics if Vosk returns a result this crate cannot parse. Use [`try_front_result`]
+    /// to handle that case instead.
+    ///
+    /// [`try_front_result`]: Self::try_front_result
+    pub fn front_result(&mut self) -> CompleteResult<'static> {
+        expect_result(self.try_front_result())
+    }
+
+    /// Like [`front_result`], but returns an error instead of panicking if Vosk returns
+    /// a result this crate cannot parse.
+    ///
+    /// [`front_result`]: Self::front_result
+    pub fn try_front_result(&mut self) -> Result<CompleteResult<'static>, ResultError> {
+        unsafe { complete_result_from_c_str(vosk_batch_recognizer_front_result(self.0.as_ptr())) }
     }
 
     /// Removes the front of the result queue.
@@ -74,12 +108,12 @@ impl BatchRecognizer {
 }
 
 // SAFETY: Recognizer shares no state, so it is Send
-unsafe impl Send for BatchRecognizer {}
+unsafe impl Send for BatchRecognizer<'_> {}
 // SAFETY: All methods that mutate Recognizer require a &mut to it,
 // which ensures exclusive access, so it is Sync
-unsafe impl Sync for BatchRecognizer {}
+unsafe impl Sync for BatchRecognizer<'_> {}
 
-impl Drop for BatchRecognizer {
+impl Drop for BatchRecognizer<'_> {
     fn drop(&mut self) {
         unsafe { vosk_batch_recognizer_free(self.0.as_ptr()) }
     }
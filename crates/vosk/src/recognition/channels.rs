@@ -0,0 +1,110 @@
+use super::{samples::Waveform, AcceptWaveformError};
+
+/// How audio with several channels is turned into the single channel Vosk recognizes
+/// (see [`Recognizer::set_input_channels`]).
+///
+/// [`Recognizer::set_input_channels`]: crate::Recognizer::set_input_channels
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ChannelMix {
+    /// The average of all the channels.
+    #[default]
+    Average,
+    /// A single channel, by index (starting at 0), ignoring the others.
+    ///
+    /// In stereo audio, the left channel is 0 and the right one is 1.
+    Channel(u16),
+    /// The loudest channel of each buffer, the one with the greatest RMS amplitude,
+    /// ignoring the others. Suited to recordings where each speaker has their own channel.
+    ///
+    /// The channel is picked again for each buffer passed to the recognizer, so the
+    /// buffers should be long enough to hold a few words (a second or so).
+    Loudest,
+}
+
+/// Layout of the audio a recognizer receives.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct InputChannels {
+    channels: u16,
+    mix: ChannelMix,
+}
+
+impl Default for InputChannels {
+    fn default() -> Self {
+        Self {
+            channels: 1,
+            mix: ChannelMix::Average,
+        }
+    }
+}
+
+impl InputChannels {
+    /// Returns the layout, or [`None`] if there are no channels or `mix` selects
+    /// a channel that does not exist.
+    pub(crate) fn new(channels: u16, mix: ChannelMix) -> Option<Self> {
+        let valid = match mix {
+            ChannelMix::Channel(channel) => channel < channels,
+            ChannelMix::Average | ChannelMix::Loudest => channels > 0,
+        };
+
+        valid.then_some(Self { channels, mix })
+    }
+
+    /// Mixes the interleaved channels of `waveform` into a single one, in `buffer` if needed.
+    pub(crate) fn mix<'a>(
+        &self,
+        waveform: Waveform<'a>,
+        buffer: &'a mut Vec<f32>,
+    ) -> Result<Waveform<'a>, AcceptWaveformError> {
+        let channels = usize::from(self.channels);
+        if channels == 1 {
+            return Ok(waveform);
+        }
+
+        let len = waveform.len();
+        // usize::is_multiple_of needs Rust 1.87
+        #[allow(clippy::manual_is_multiple_of)]
+        if len % channels != 0 {
+            return Err(AcceptWaveformError::PartialFrame {
+                samples: len,
+                channels: self.channels,
+            });
+        }
+
+        let frames = (0..len).step_by(channels);
+
+        buffer.clear();
+        match self.mix {
+            ChannelMix::Average => buffer.extend(frames.map(|frame| {
+                (frame..frame + channels)
+                    .map(|index| waveform.get(index))
+                    .sum::<f32>()
+                    / f32::from(self.channels)
+            })),
+            ChannelMix::Channel(channel) => {
+                buffer.extend(frames.map(|frame| waveform.get(frame + usize::from(channel))))
+            }
+            ChannelMix::Loudest => {
+                // Comparing the energies picks the same channel as comparing the RMS amplitudes,
+                // as all the channels have as many samples
+                let energy = |channel: usize| {
+                    frames
+                        .clone()
+                        .map(|frame| waveform.get(frame + channel).powi(2))
+                        .sum::<f32>()
+                };
+                let (loudest, _) = (1..channels).fold((0, energy(0)), |loudest, channel| {
+                    let channel = (channel, energy(channel));
+                    if channel.1 > loudest.1 {
+                        channel
+                    } else {
+                        loudest
+                    }
+                });
+
+                buffer.extend(frames.map(|frame| waveform.get(frame + loudest)))
+            }
+        }
+
+        Ok(Waveform::Float(buffer))
+    }
+}
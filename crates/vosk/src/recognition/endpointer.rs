@@ -0,0 +1,69 @@
+use std::time::Duration;
+use vosk_sys::*;
+
+/// How long the silence after speech must be for the recognizer to consider that an utterance
+/// ended (see [`DecodingState::Finalized`] and [`Recognizer::set_endpointer_mode`]).
+///
+/// Vosk scales the trailing silence of the endpoint rules of the model
+/// (see [`EndpointConfig`]) according to the mode.
+///
+/// [`DecodingState::Finalized`]: crate::DecodingState::Finalized
+/// [`Recognizer::set_endpointer_mode`]: crate::Recognizer::set_endpointer_mode
+/// [`EndpointConfig`]: crate::EndpointConfig
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EndpointerMode {
+    /// The endpoint rules of the model, unchanged.
+    #[default]
+    Default,
+    /// Shorter pauses end an utterance. Suited to short answers and commands.
+    Short,
+    /// Longer pauses are needed to end an utterance.
+    Long,
+    /// Even longer pauses are needed to end an utterance. Suited to dictation.
+    VeryLong,
+}
+
+impl EndpointerMode {
+    /// Returns the value that corresponds to the variant in C.
+    pub(super) fn to_c(self) -> VoskEndpointerMode {
+        match self {
+            Self::Default => VoskEndpointerMode_VOSK_EP_ANSWER_DEFAULT,
+            Self::Short => VoskEndpointerMode_VOSK_EP_ANSWER_SHORT,
+            Self::Long => VoskEndpointerMode_VOSK_EP_ANSWER_LONG,
+            Self::VeryLong => VoskEndpointerMode_VOSK_EP_ANSWER_VERY_LONG,
+        }
+    }
+}
+
+/// Timeouts that end an utterance, overriding the endpoint rules of the model
+/// (see [`Recognizer::set_endpointer_delays`]).
+///
+/// # Examples
+///
+/// ```
+/// # use std::time::Duration;
+/// # use vosk::EndpointerDelays;
+/// #
+/// // Dictation: allow pauses to think in the middle of a sentence
+/// let delays = EndpointerDelays {
+///     initial_silence: Duration::from_secs(10),
+///     trailing_silence: Duration::from_millis(2500),
+///     max_utterance: Duration::from_secs(60),
+/// };
+/// ```
+///
+/// [`Recognizer::set_endpointer_delays`]: crate::Recognizer::set_endpointer_delays
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EndpointerDelays {
+    /// Silence after which the utterance ends if nothing was recognized yet.
+    /// Usually around 5 seconds.
+    pub initial_silence: Duration,
+
+    /// Silence after which the utterance ends once something was recognized.
+    /// Usually between 0.5 and 1 second.
+    pub trailing_silence: Duration,
+
+    /// Length after which the utterance is ended, even if there was no silence.
+    /// Usually between 20 and 30 seconds.
+    pub max_utterance: Duration,
+}
@@ -1,3 +1,6 @@
+use super::ChannelMix;
+use crate::grammar::GrammarError;
+use std::{borrow::Cow, path::PathBuf, str::Utf8Error};
 use thiserror::Error;
 
 /// Possible errors that accept_waveform methods might return.
@@ -10,4 +13,113 @@ pub enum AcceptWaveformError {
         i32::MAX
     )]
     BufferTooLong(usize),
+
+    /// Error returned if the user passes in a buffer of bytes of 16-bit audio
+    /// whose length is odd, so it does not contain whole samples.
+    #[error("the length of the provided audio buffer was {0} bytes (expected an even number)")]
+    OddByteCount(usize),
+
+    /// Error returned if the user passes in a buffer that does not contain a whole number
+    /// of frames, with one sample for each channel set with [`Recognizer::set_input_channels`].
+    ///
+    /// [`Recognizer::set_input_channels`]: crate::Recognizer::set_input_channels
+    #[error("the provided audio buffer has {samples} samples, which is not a multiple of the {channels} channels")]
+    PartialFrame {
+        /// Number of samples in the buffer.
+        samples: usize,
+        /// Number of channels.
+        channels: u16,
+    },
+}
+
+/// Possible errors that recognizer constructors and configuration methods, such as
+/// [`Recognizer::set_grammar`], might return.
+///
+/// [`Recognizer::set_grammar`]: crate::Recognizer::set_grammar
+#[derive(Error, Debug)]
+pub enum RecognizerError {
+    /// Error returned if the grammar cannot be passed to Vosk.
+    #[error("invalid grammar")]
+    Grammar(#[from] GrammarError),
+
+    /// Error returned if a grammar is used with a model that only has a static
+    /// (`HCLG.fst`) graph. Grammars need a lookahead graph (`HCLr.fst` and `Gr.fst`).
+    #[error("the model at {0:?} does not have a lookahead graph, so it does not support grammars")]
+    GrammarNotSupported(PathBuf),
+
+    /// Error returned if Vosk could not create the recognizer.
+    /// The reason will have been logged by Vosk (see [`set_log_level`]).
+    ///
+    /// [`set_log_level`]: crate::set_log_level
+    #[error("vosk failed to create the recognizer")]
+    CreationFailed,
+
+    /// Error returned if audio is said to have no channels, or the channel to recognize
+    /// does not exist.
+    #[error("cannot use {mix:?} with {channels} channels")]
+    InvalidChannels {
+        /// Number of channels.
+        channels: u16,
+        /// How the channels were to be mixed.
+        mix: ChannelMix,
+    },
+
+    /// Error returned if a sample rate is not a positive number.
+    #[error("invalid sample rate {0}")]
+    InvalidSampleRate(f32),
+}
+
+/// Possible errors that [`Recognizer::try_result`] and the other methods that retrieve
+/// results might return.
+///
+/// They mean Vosk returned something this crate does not understand, such as a result with
+/// a shape added in a newer version, so they should be reported. The raw text Vosk returned
+/// is kept to help with that.
+///
+/// [`Recognizer::try_result`]: crate::Recognizer::try_result
+#[derive(Error, Debug)]
+pub enum ResultError {
+    /// Error returned if the result is not valid UTF-8.
+    #[error("vosk returned a result that is not valid UTF-8: {:?}", String::from_utf8_lossy(.raw))]
+    InvalidUtf8 {
+        /// Raw bytes of the result.
+        raw: Vec<u8>,
+        /// Underlying error.
+        #[source]
+        source: Utf8Error,
+    },
+
+    /// Error returned if the result is not valid JSON, or does not have the expected shape.
+    #[error("vosk returned a result that could not be parsed: {raw:?}")]
+    InvalidJson {
+        /// Raw text of the result.
+        raw: String,
+        /// Underlying error.
+        #[source]
+        source: serde_json::Error,
+    },
+
+    /// Error returned if an NLSML result (see [`Recognizer::set_nlsml`]) is not valid XML,
+    /// or does not have the expected shape.
+    ///
+    /// [`Recognizer::set_nlsml`]: crate::Recognizer::set_nlsml
+    #[error("vosk returned an NLSML result that could not be parsed ({message}): {raw:?}")]
+    InvalidNlsml {
+        /// Raw text of the result.
+        raw: String,
+        /// Description of the problem.
+        message: String,
+    },
+}
+
+impl ResultError {
+    /// The raw text of the result. Invalid UTF-8 sequences are replaced with
+    /// [`U+FFFD REPLACEMENT CHARACTER`](char::REPLACEMENT_CHARACTER).
+    #[must_use]
+    pub fn raw_text(&self) -> Cow<'_, str> {
+        match self {
+            Self::InvalidUtf8 { raw, .. } => String::from_utf8_lossy(raw),
+            Self::InvalidJson { raw, .. } | Self::InvalidNlsml { raw, .. } => Cow::Borrowed(raw),
+        }
+    }
 }
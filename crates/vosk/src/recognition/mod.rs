@@ -2,15 +2,34 @@ use std::os::raw::c_int;
 
 #[cfg(feature = "batch")]
 mod batch;
+mod channels;
+mod endpointer;
 mod errors;
+mod nlsml;
+mod resampler;
 mod results;
+mod samples;
+mod scripted;
 mod sequential;
+mod speech_recognizer;
+mod transcriber;
 
 #[cfg(feature = "batch")]
 pub use batch::BatchRecognizer;
+pub use channels::ChannelMix;
+pub use endpointer::{EndpointerDelays, EndpointerMode};
 pub use errors::*;
+pub use nlsml::{NlsmlInterpretation, NlsmlResult};
 pub use results::*;
+pub use samples::AudioSample;
+pub use scripted::ScriptedRecognizer;
 pub use sequential::Recognizer;
+pub use speech_recognizer::SpeechRecognizer;
+pub use transcriber::{Event, Transcriber, TranscriberError};
+
+// Used by transcribe_file_with to convert the audio of files
+#[cfg(feature = "audio")]
+pub(crate) use {channels::InputChannels, resampler::Resampler, samples::Waveform};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 /// State of the decodification after processing a chunk of data.
@@ -0,0 +1,161 @@
+//! Parser for the [NLSML](https://www.w3.org/TR/nl-spec/) results Vosk returns
+//! when NLSML output is enabled.
+
+use super::ResultError;
+use roxmltree::{Document, Node};
+use serde::{Deserialize, Serialize};
+
+/// Recognition result if [`Recognizer::set_nlsml`] (or `BatchRecognizer::set_nlsml`)
+/// is passed `true`.
+///
+/// Inner type of [`CompleteResult::Nlsml`].
+///
+/// Vosk returns one interpretation, or one per alternative if
+/// [`Recognizer::set_max_alternatives`] was passed a non-zero value, ordered from most
+/// to less likely.
+///
+/// [`Recognizer::set_nlsml`]: crate::Recognizer::set_nlsml
+/// [`Recognizer::set_max_alternatives`]: crate::Recognizer::set_max_alternatives
+/// [`CompleteResult::Nlsml`]: crate::CompleteResult::Nlsml
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NlsmlResult {
+    /// Grammar the result was matched against (`"default"` for Vosk).
+    pub grammar: Option<String>,
+
+    /// Possible interpretations of the speech.
+    pub interpretations: Vec<NlsmlInterpretation>,
+
+    /// The XML document the result was parsed from.
+    pub raw_xml: String,
+}
+
+/// An interpretation in a [`NlsmlResult`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NlsmlInterpretation {
+    /// Grammar the interpretation was matched against.
+    pub grammar: Option<String>,
+
+    /// Confidence of the recognizer in this interpretation.
+    ///
+    /// For Vosk, it is the same score as [`Alternative::confidence`], not a probability.
+    ///
+    /// [`Alternative::confidence`]: crate::Alternative::confidence
+    pub confidence: f32,
+
+    /// Text of the input, or [`None`] if no speech was recognized (`<noinput/>`).
+    pub input: Option<String>,
+
+    /// Mode of the input, such as `"speech"`.
+    pub input_mode: Option<String>,
+
+    /// Semantic interpretation of the input. Vosk puts the text of the input in it.
+    pub instance: String,
+}
+
+impl NlsmlResult {
+    /// Parses an NLSML document.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use vosk::NlsmlResult;
+    /// #
+    /// let result = NlsmlResult::from_xml(
+    ///     r#"<?xml version="1.0"?>
+    ///     <result grammar="default">
+    ///         <interpretation grammar="default" confidence="229.79">
+    ///             <input mode="speech">turn on the lights</input>
+    ///             <instance>turn on the lights</instance>
+    ///         </interpretation>
+    ///     </result>"#,
+    /// )
+    /// .unwrap();
+    ///
+    /// assert_eq!(result.text(), Some("turn on the lights"));
+    /// assert_eq!(result.best().unwrap().confidence, 229.79);
+    /// ```
+    pub fn from_xml(xml: &str) -> Result<Self, ResultError> {
+        parse(xml).map_err(|message| ResultError::InvalidNlsml {
+            raw: xml.to_owned(),
+            message,
+        })
+    }
+
+    /// The most likely interpretation, if any.
+    #[must_use]
+    pub fn best(&self) -> Option<&NlsmlInterpretation> {
+        self.interpretations.first()
+    }
+
+    /// Text of the input of the most likely interpretation, if any speech was recognized.
+    #[must_use]
+    pub fn text(&self) -> Option<&str> {
+        self.best()?.input.as_deref()
+    }
+}
+
+fn parse(xml: &str) -> Result<NlsmlResult, String> {
+    let document = Document::parse(xml).map_err(|error| error.to_string())?;
+
+    let result = document.root_element();
+    if result.tag_name().name() != "result" {
+        return Err(format!(
+            "expected <result>, found <{}>",
+            result.tag_name().name()
+        ));
+    }
+
+    let interpretations = result
+        .children()
+        .filter(|node| node.has_tag_name("interpretation"))
+        .map(parse_interpretation)
+        .collect::<Result<_, _>>()?;
+
+    Ok(NlsmlResult {
+        grammar: result.attribute("grammar").map(str::to_owned),
+        interpretations,
+        raw_xml: xml.to_owned(),
+    })
+}
+
+fn parse_interpretation(node: Node) -> Result<NlsmlInterpretation, String> {
+    let confidence = node
+        .attribute("confidence")
+        .ok_or("<interpretation> without a confidence")?;
+    let confidence = confidence
+        .trim()
+        .parse()
+        .map_err(|_| format!("invalid confidence {confidence:?}"))?;
+
+    let input = node
+        .children()
+        .find(|child| child.has_tag_name("input"))
+        .ok_or("<interpretation> without an <input>")?;
+    let has_no_input = input
+        .children()
+        .any(|child| child.has_tag_name("noinput") || child.has_tag_name("nomatch"));
+
+    let instance = node
+        .children()
+        .find(|child| child.has_tag_name("instance"))
+        .map(text)
+        .unwrap_or_default();
+
+    Ok(NlsmlInterpretation {
+        grammar: node.attribute("grammar").map(str::to_owned),
+        confidence,
+        input: (!has_no_input).then(|| text(input)),
+        input_mode: input.attribute("mode").map(str::to_owned),
+        instance,
+    })
+}
+
+/// Text inside `node` and its descendants, with surrounding whitespace removed.
+fn text(node: Node) -> String {
+    node.descendants()
+        .filter(Node::is_text)
+        .filter_map(|node| node.text())
+        .collect::<String>()
+        .trim()
+        .to_owned()
+}
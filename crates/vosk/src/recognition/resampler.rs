@@ -0,0 +1,152 @@
+use super::samples::Waveform;
+use std::f64::consts::PI;
+
+/// Zero crossings of the sinc filter on each side of its center, at the cutoff frequency.
+/// More give a sharper cutoff, at the cost of latency and computation.
+const ZERO_CROSSINGS: usize = 16;
+
+/// Points of the filter table per input sample. The filter is linearly interpolated between them.
+const TABLE_RESOLUTION: usize = 256;
+
+/// Streaming sample rate converter, using a windowed sinc filter (band-limited interpolation).
+///
+/// The state is kept between chunks, so audio can be resampled in chunks of any size
+/// (down to a single sample) with the same result as all at once.
+#[derive(Debug, Clone)]
+pub(crate) struct Resampler {
+    /// Input samples per output sample.
+    step: f64,
+    /// Number of input samples on each side of an output sample it is computed from.
+    half_width: usize,
+    /// Right half of the low-pass filter, every `1 / TABLE_RESOLUTION` input samples from
+    /// its center, followed by a 0 at `half_width`.
+    kernel: Vec<f64>,
+    /// Input samples that are still needed, starting with silence before the first one.
+    input: Vec<f32>,
+    /// Index in `input` of the sample before the next output sample.
+    index: usize,
+    /// Position of the next output sample after `index`, between 0 and 1. Kept apart from
+    /// `index` so the rounding does not depend on how much of `input` was dropped.
+    fraction: f64,
+    /// Buffer for the resampled audio.
+    output: Vec<f32>,
+}
+
+impl Resampler {
+    /// Creates a resampler from `input_rate` to `output_rate`, which must be positive.
+    pub(crate) fn new(input_rate: f32, output_rate: f32) -> Self {
+        let step = f64::from(input_rate) / f64::from(output_rate);
+        // Relative to the input Nyquist frequency. Below 1 when downsampling, to remove
+        // the frequencies the output cannot represent
+        let cutoff = step.recip().min(1.0);
+        let half_width = (ZERO_CROSSINGS as f64 / cutoff).ceil() as usize;
+
+        let kernel = (0..=half_width * TABLE_RESOLUTION)
+            .map(|point| kernel(point as f64 / TABLE_RESOLUTION as f64, cutoff, half_width))
+            .collect();
+
+        let mut resampler = Self {
+            step,
+            half_width,
+            kernel,
+            input: Vec::new(),
+            index: 0,
+            fraction: 0.0,
+            output: Vec::new(),
+        };
+        resampler.reset();
+        resampler
+    }
+
+    /// Discards the audio that was not fully resampled yet.
+    pub(crate) fn reset(&mut self) {
+        self.input.clear();
+        self.input.resize(self.half_width, 0.0);
+        self.index = self.half_width;
+        self.fraction = 0.0;
+    }
+
+    /// Resamples a chunk of audio.
+    ///
+    /// The last samples of the chunk are kept until the next one, since the output samples
+    /// around them also depend on the audio that follows.
+    pub(crate) fn process(&mut self, waveform: Waveform<'_>) -> Waveform<'_> {
+        self.input
+            .extend((0..waveform.len()).map(|index| waveform.get(index)));
+        self.resample();
+
+        Waveform::Float(&self.output)
+    }
+
+    /// Resamples the samples kept from the previous chunks, followed by silence,
+    /// and resets the resampler.
+    ///
+    /// This is the end of the audio passed to [`process`](Self::process) so far.
+    pub(crate) fn flush(&mut self) -> &[f32] {
+        self.input.resize(self.input.len() + self.half_width, 0.0);
+        self.resample();
+        self.reset();
+
+        &self.output
+    }
+
+    /// Computes the output samples `input` has all the samples of, into `output`.
+    fn resample(&mut self) {
+        self.output.clear();
+        while self.index + self.half_width < self.input.len() {
+            self.output.push(self.interpolate());
+
+            self.fraction += self.step;
+            let whole = self.fraction.floor();
+            self.index += whole as usize;
+            self.fraction -= whole;
+        }
+
+        // Drop the samples no output sample will be computed from anymore
+        let first_needed = (self.index + 1).saturating_sub(self.half_width);
+        self.input.drain(..first_needed);
+        self.index -= first_needed;
+    }
+
+    /// Value of the signal at the next output sample, which needs the `half_width` input
+    /// samples on each side of it.
+    fn interpolate(&self) -> f32 {
+        let first = self.index + 1 - self.half_width;
+        let last = self.index + self.half_width;
+
+        let value: f64 = (first..=last)
+            .map(|index| {
+                let distance = (self.index as f64 - index as f64) + self.fraction;
+                f64::from(self.input[index]) * self.kernel_at(distance)
+            })
+            .sum();
+        value as f32
+    }
+
+    /// Low-pass filter at `distance` input samples from its center, interpolated from the table.
+    fn kernel_at(&self, distance: f64) -> f64 {
+        let position = distance.abs() * TABLE_RESOLUTION as f64;
+        let point = position as usize;
+        if point >= self.kernel.len() - 1 {
+            return 0.0;
+        }
+
+        let weight = position - point as f64;
+        self.kernel[point] * (1.0 - weight) + self.kernel[point + 1] * weight
+    }
+}
+
+/// Low-pass filter at `distance` input samples from its center: a sinc at `cutoff`
+/// (relative to the input Nyquist frequency), tapered to 0 at `half_width` with a Hann window.
+fn kernel(distance: f64, cutoff: f64, half_width: usize) -> f64 {
+    let width = half_width as f64;
+    if distance.abs() >= width {
+        return 0.0;
+    }
+
+    let x = PI * cutoff * distance;
+    let sinc = if x == 0.0 { 1.0 } else { x.sin() / x };
+    let window = 0.5 + 0.5 * (PI * distance / width).cos();
+
+    cutoff * sinc * window
+}
@@ -1,8 +1,12 @@
 //! Results of a recognition
 
-use std::ffi::{c_char, CStr};
+use std::{
+    borrow::Cow,
+    ffi::{c_char, CStr},
+};
 
-use serde::{Deserialize, Serialize};
+use super::{NlsmlResult, ResultError};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 
 /// A single word in a [`CompleteResultSingle`] and metadata about it.
 ///
@@ -22,7 +26,18 @@ pub struct Word<'a> {
     pub end: f32,
 
     /// The transcribed word.
-    pub word: &'a str,
+    pub word: Cow<'a, str>,
+}
+
+impl Word<'_> {
+    /// Returns a copy that owns its strings, see [`CompleteResult::into_owned`].
+    #[must_use]
+    pub fn into_owned(self) -> Word<'static> {
+        Word {
+            word: Cow::Owned(self.word.into_owned()),
+            ..self
+        }
+    }
 }
 
 /// A single word in an [`Alternative`] and metadata about it.
@@ -38,7 +53,18 @@ pub struct WordInAlternative<'a> {
     pub end: f32,
 
     /// The transcribed word.
-    pub word: &'a str,
+    pub word: Cow<'a, str>,
+}
+
+impl WordInAlternative<'_> {
+    /// Returns a copy that owns its strings, see [`CompleteResult::into_owned`].
+    #[must_use]
+    pub fn into_owned(self) -> WordInAlternative<'static> {
+        WordInAlternative {
+            word: Cow::Owned(self.word.into_owned()),
+            ..self
+        }
+    }
 }
 
 /// An alternative transcript in a [`CompleteResultMultiple`].
@@ -57,7 +83,23 @@ pub struct Alternative<'a> {
     pub result: Vec<WordInAlternative<'a>>,
 
     /// Full transcript text.
-    pub text: &'a str,
+    pub text: Cow<'a, str>,
+}
+
+impl Alternative<'_> {
+    /// Returns a copy that owns its strings, see [`CompleteResult::into_owned`].
+    #[must_use]
+    pub fn into_owned(self) -> Alternative<'static> {
+        Alternative {
+            confidence: self.confidence,
+            result: self
+                .result
+                .into_iter()
+                .map(WordInAlternative::into_owned)
+                .collect(),
+            text: Cow::Owned(self.text.into_owned()),
+        }
+    }
 }
 
 /// Recognition result if [`Recognizer::set_max_alternatives`]
@@ -65,14 +107,61 @@ pub struct Alternative<'a> {
 ///
 /// Inner type of [`CompleteResult::Multiple`].
 ///
+/// When the recognizer has a speaker model, the speaker information is next to the
+/// alternatives, as it describes the audio rather than any alternative:
+///
+/// ```
+/// # use vosk::CompleteResult;
+/// #
+/// let json = r#"{
+///     "alternatives": [
+///         {"confidence": 229.79, "text": "one zero"},
+///         {"confidence": 226.47, "text": "one zero nah"}
+///     ],
+///     "spk": [-0.64, 0.27, 1.21],
+///     "spk_frames": 415
+/// }"#;
+///
+/// let result = serde_json::from_str::<CompleteResult>(json).unwrap().multiple().unwrap();
+/// assert_eq!(result.alternatives[0].text, "one zero");
+/// assert_eq!(result.speaker_info.unwrap().frames, 415);
+/// ```
+///
 /// [`Recognizer::set_max_alternatives`]: crate::Recognizer::set_max_alternatives
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct CompleteResultMultiple<'a> {
+    /// Information about the speaker, used for speaker identification
+    ///
+    /// Enabled if the [`Recognizer`] was passed a [`SpeakerModel`]  with
+    /// [`Recognizer::new_with_speaker`] or [`Recognizer::set_speaker_model`],
+    /// [`None`] otherwise
+    ///
+    /// [`SpeakerModel`]: crate::SpeakerModel
+    /// [`Recognizer`]: crate::Recognizer
+    /// [`Recognizer::new_with_speaker`]: crate::Recognizer::new_with_speaker
+    /// [`Recognizer::set_speaker_model`]: crate::Recognizer::set_speaker_model
+    #[serde(flatten)]
+    pub speaker_info: Option<SpeakerInfo>,
+
     /// All the possible results of the transcription, ordered from most to less likely.
-    #[serde(borrow)]
     pub alternatives: Vec<Alternative<'a>>,
 }
 
+impl CompleteResultMultiple<'_> {
+    /// Returns a copy that owns its strings, see [`CompleteResult::into_owned`].
+    #[must_use]
+    pub fn into_owned(self) -> CompleteResultMultiple<'static> {
+        CompleteResultMultiple {
+            speaker_info: self.speaker_info,
+            alternatives: self
+                .alternatives
+                .into_iter()
+                .map(Alternative::into_owned)
+                .collect(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 /// Data useful for speaker identification.
 pub struct SpeakerInfo {
@@ -91,7 +180,7 @@ pub struct SpeakerInfo {
 /// Inner type of [`CompleteResult::Single`].
 ///
 /// [`Recognizer::set_max_alternatives`]: crate::Recognizer::set_max_alternatives
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
 pub struct CompleteResultSingle<'a> {
     /// Information about the speaker, used for speaker identification
     ///
@@ -116,12 +205,40 @@ pub struct CompleteResultSingle<'a> {
     pub result: Vec<Word<'a>>,
 
     /// Full text of the transcript.
-    pub text: &'a str,
+    pub text: Cow<'a, str>,
+}
+
+impl CompleteResultSingle<'_> {
+    /// Returns a copy that owns its strings, see [`CompleteResult::into_owned`].
+    #[must_use]
+    pub fn into_owned(self) -> CompleteResultSingle<'static> {
+        CompleteResultSingle {
+            speaker_info: self.speaker_info,
+            result: self.result.into_iter().map(Word::into_owned).collect(),
+            text: Cow::Owned(self.text.into_owned()),
+        }
+    }
 }
 
 /// Different results that can be returned from
 /// [`Recognizer::result`] and [`Recognizer::final_result`].
 ///
+/// Recognizers return a `CompleteResult<'static>`, which owns its strings, so it can be
+/// stored or sent to another thread. The strings are [`Cow`]s so that results built by hand,
+/// for example to be serialized, can borrow them instead, and
+/// [`into_owned`](Self::into_owned) turns such a result into an owned one.
+/// Deserializing always produces owned strings, so results can also be read from owned JSON,
+/// such as a [`serde_json::Value`] or a reader:
+///
+/// ```
+/// # use vosk::CompleteResult;
+/// #
+/// let json = serde_json::json!({"text": "hello world"});
+/// let result: CompleteResult<'static> = serde_json::from_value(json).unwrap();
+///
+/// assert_eq!(result.single().unwrap().text, "hello world");
+/// ```
+///
 /// [`Recognizer::result`]: crate::Recognizer::result
 /// [`Recognizer::final_result`]: crate::Recognizer::final_result
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -130,13 +247,20 @@ pub enum CompleteResult<'a> {
     /// Result if [`Recognizer::set_max_alternatives`] is passed zero (default).
     ///
     /// [`Recognizer::set_max_alternatives`]: crate::Recognizer::set_max_alternatives
-    #[serde(borrow)]
     Single(CompleteResultSingle<'a>),
 
     /// Result if [`Recognizer::set_max_alternatives`] is passed a non-zero value.
     ///
     /// [`Recognizer::set_max_alternatives`]: crate::Recognizer::set_max_alternatives
     Multiple(CompleteResultMultiple<'a>),
+
+    /// Result if [`Recognizer::set_nlsml`] is passed `true`.
+    ///
+    /// It is never deserialized from JSON, as Vosk returns it as XML.
+    ///
+    /// [`Recognizer::set_nlsml`]: crate::Recognizer::set_nlsml
+    #[serde(skip_deserializing)]
+    Nlsml(NlsmlResult),
 }
 
 impl<'a> CompleteResult<'a> {
@@ -147,7 +271,7 @@ impl<'a> CompleteResult<'a> {
     pub fn single(self) -> Option<CompleteResultSingle<'a>> {
         match self {
             Self::Single(x) => Some(x),
-            Self::Multiple(_) => None,
+            Self::Multiple(_) | Self::Nlsml(_) => None,
         }
     }
 
@@ -157,8 +281,41 @@ impl<'a> CompleteResult<'a> {
     #[must_use]
     pub fn multiple(self) -> Option<CompleteResultMultiple<'a>> {
         match self {
-            Self::Single(_) => None,
             Self::Multiple(x) => Some(x),
+            Self::Single(_) | Self::Nlsml(_) => None,
+        }
+    }
+
+    /// Returns the inner [`NlsmlResult`] if `self` was [`Nlsml`], and [`None`] otherwise.
+    ///
+    /// [`Nlsml`]: Self::Nlsml
+    #[must_use]
+    pub fn nlsml(self) -> Option<NlsmlResult> {
+        match self {
+            Self::Nlsml(x) => Some(x),
+            Self::Single(_) | Self::Multiple(_) => None,
+        }
+    }
+
+    /// Text of the result, or of its most likely alternative or interpretation.
+    ///
+    /// Returns [`None`] if there are no alternatives, or no speech was recognized in NLSML.
+    #[must_use]
+    pub fn text(&self) -> Option<&str> {
+        match self {
+            Self::Single(x) => Some(&x.text),
+            Self::Multiple(x) => x.alternatives.first().map(|alternative| &*alternative.text),
+            Self::Nlsml(x) => x.text(),
+        }
+    }
+
+    /// Copies the strings the result borrows, so it can outlive the data it borrows from.
+    #[must_use]
+    pub fn into_owned(self) -> CompleteResult<'static> {
+        match self {
+            Self::Single(x) => CompleteResult::Single(x.into_owned()),
+            Self::Multiple(x) => CompleteResult::Multiple(x.into_owned()),
+            Self::Nlsml(x) => CompleteResult::Nlsml(x),
         }
     }
 }
@@ -166,14 +323,17 @@ impl<'a> CompleteResult<'a> {
 /// Result returned by [`Recognizer::partial_result`].
 /// The result may change after processing more data as decoding is not yet complete.
 ///
+/// Like [`CompleteResult`], it owns its strings when returned by a recognizer, and
+/// [`into_owned`](Self::into_owned) copies the strings of one built by hand.
+///
 /// [`Recognizer::partial_result`]: crate::Recognizer::partial_result
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
 pub struct PartialResult<'a> {
     // The "partial" JSON key will not be present if partial_result is called when the recognizer isn't running (DecodingState::Running).
     // It makes sense to return an empty string in that case
     /// Full text of the partial transcript.
     #[serde(default)]
-    pub partial: &'a str,
+    pub partial: Cow<'a, str>,
 
     /// Collection of words present in [`partial`] with metadata about them.
     ///
@@ -185,11 +345,59 @@ pub struct PartialResult<'a> {
     pub partial_result: Vec<Word<'a>>,
 }
 
+impl PartialResult<'_> {
+    /// Copies the strings the result borrows, so it can outlive the data it borrows from.
+    #[must_use]
+    pub fn into_owned(self) -> PartialResult<'static> {
+        PartialResult {
+            partial: Cow::Owned(self.partial.into_owned()),
+            partial_result: self
+                .partial_result
+                .into_iter()
+                .map(Word::into_owned)
+                .collect(),
+        }
+    }
+}
+
 /// Generic function to retrieve a given type of result from the recognizer.
-pub(super) unsafe fn result_from_json_c_str<'de, T: Deserialize<'de>>(ptr: *const c_char) -> T {
-    // UNWRAP: Panics in here will never be the caller's fault, but rather some
-    // edge case that was not thought of and should be reported, so it does not
-    // make sense to return a Result.
-    let string = CStr::from_ptr(ptr).to_str().unwrap();
-    serde_json::from_str(string).unwrap_or_else(|_| panic!("Invalid JSON: {string:?}"))
+///
+/// The result owns its strings, as Vosk overwrites the string at `ptr` on the next call.
+pub(super) unsafe fn result_from_json_c_str<T: DeserializeOwned>(
+    ptr: *const c_char,
+) -> Result<T, ResultError> {
+    let string = str_from_c_str(ptr)?;
+
+    serde_json::from_str(string).map_err(|source| ResultError::InvalidJson {
+        raw: string.to_owned(),
+        source,
+    })
+}
+
+/// Retrieves a complete result from the recognizer, which is XML in NLSML mode and JSON otherwise.
+pub(super) unsafe fn complete_result_from_c_str(
+    ptr: *const c_char,
+) -> Result<CompleteResult<'static>, ResultError> {
+    let string = str_from_c_str(ptr)?;
+
+    if string.trim_start().starts_with('<') {
+        NlsmlResult::from_xml(string).map(CompleteResult::Nlsml)
+    } else {
+        result_from_json_c_str(ptr)
+    }
+}
+
+unsafe fn str_from_c_str<'a>(ptr: *const c_char) -> Result<&'a str, ResultError> {
+    let bytes = CStr::from_ptr(ptr).to_bytes();
+    std::str::from_utf8(bytes).map_err(|source| ResultError::InvalidUtf8 {
+        raw: bytes.to_vec(),
+        source,
+    })
+}
+
+/// Returns the result, or panics with `error`.
+pub(super) fn expect_result<T>(result: Result<T, ResultError>) -> T {
+    // Panics in here will never be the caller's fault, but rather some
+    // edge case that was not thought of and should be reported
+    result.unwrap_or_else(|error| panic!("{error}"))
 }
@@ -0,0 +1,192 @@
+use super::AcceptWaveformError;
+
+/// A type of audio sample [`Recognizer::accept_samples`] accepts. It is implemented for:
+/// * [`i16`]: 16-bit PCM, which Vosk works with.
+/// * [`f32`] and [`f64`]: samples between `-1.0` and `1.0`, as produced by most audio libraries.
+///   [`f32`] samples are passed to Vosk without being converted to 16-bit.
+/// * [`i32`]: 32-bit PCM. Untyped integer literals are inferred as [`i32`], so 16-bit samples
+///   written as literals should be given the [`i16`] type, or passed to
+///   [`Recognizer::accept_waveform`].
+/// * [`u16`]: unsigned 16-bit PCM, whose silence is `32768`.
+/// * [`u8`] and [`i8`]: the bytes of 16-bit little-endian PCM, as read from a file or
+///   the network, whatever the byte order of the target (they are converted on big-endian ones).
+///   A buffer with an odd number of bytes is rejected with [`AcceptWaveformError::OddByteCount`].
+///
+/// Samples that Vosk cannot take directly are converted in a buffer the recognizer reuses,
+/// so accepting them does not allocate after the first chunks.
+///
+/// This trait is sealed: it cannot be implemented outside of this crate.
+///
+/// [`Recognizer::accept_samples`]: crate::Recognizer::accept_samples
+/// [`Recognizer::accept_waveform`]: crate::Recognizer::accept_waveform
+pub trait AudioSample: Copy + private::Sealed {}
+
+impl AudioSample for i16 {}
+impl AudioSample for f32 {}
+impl AudioSample for f64 {}
+impl AudioSample for i32 {}
+impl AudioSample for u16 {}
+impl AudioSample for u8 {}
+impl AudioSample for i8 {}
+
+pub(super) use private::Scratch;
+pub(crate) use private::Waveform;
+
+mod private {
+    use super::AcceptWaveformError;
+
+    /// Audio in one of the forms Vosk takes.
+    pub enum Waveform<'a> {
+        /// 16-bit PCM.
+        Pcm16(&'a [i16]),
+        /// 16-bit PCM, as floats (between `-32768.0` and `32767.0`).
+        Float(&'a [f32]),
+        /// Bytes of 16-bit PCM in the native byte order, so little-endian bytes are only passed
+        /// as they are on little-endian targets.
+        Bytes(&'a [u8]),
+    }
+
+    impl Waveform<'_> {
+        /// Number of samples.
+        pub fn len(&self) -> usize {
+            match self {
+                Self::Pcm16(samples) => samples.len(),
+                Self::Float(samples) => samples.len(),
+                Self::Bytes(bytes) => bytes.len() / 2,
+            }
+        }
+
+        /// Sample at `index`, as a float between `-32768.0` and `32767.0`.
+        pub fn get(&self, index: usize) -> f32 {
+            match self {
+                Self::Pcm16(samples) => f32::from(samples[index]),
+                Self::Float(samples) => samples[index],
+                Self::Bytes(bytes) => {
+                    f32::from(i16::from_ne_bytes([bytes[2 * index], bytes[2 * index + 1]]))
+                }
+            }
+        }
+    }
+
+    /// Buffers that samples are converted in.
+    #[derive(Default)]
+    pub struct Scratch {
+        pcm: Vec<i16>,
+        float: Vec<f32>,
+    }
+
+    impl Scratch {
+        fn pcm<T: Copy>(&mut self, data: &[T], convert: impl Fn(T) -> i16) -> Waveform<'_> {
+            self.pcm.clear();
+            self.pcm.extend(data.iter().map(|&sample| convert(sample)));
+            Waveform::Pcm16(&self.pcm)
+        }
+
+        fn pcm_from_le_bytes(&mut self, bytes: &[u8]) -> Waveform<'_> {
+            self.pcm.clear();
+            self.pcm.extend(
+                bytes
+                    .chunks_exact(2)
+                    .map(|sample| i16::from_le_bytes([sample[0], sample[1]])),
+            );
+            Waveform::Pcm16(&self.pcm)
+        }
+
+        fn float<T: Copy>(&mut self, data: &[T], convert: impl Fn(T) -> f32) -> Waveform<'_> {
+            self.float.clear();
+            self.float
+                .extend(data.iter().map(|&sample| convert(sample)));
+            Waveform::Float(&self.float)
+        }
+    }
+
+    pub trait Sealed: Sized {
+        /// Returns `data` in a form Vosk takes, converting it in `scratch` if needed.
+        fn waveform<'a>(
+            data: &'a [Self],
+            scratch: &'a mut Scratch,
+        ) -> Result<Waveform<'a>, AcceptWaveformError>;
+    }
+
+    /// Scale between the samples of floating point formats and 16-bit PCM.
+    const PCM16_SCALE: f32 = 32768.0;
+
+    impl Sealed for i16 {
+        fn waveform<'a>(
+            data: &'a [Self],
+            _scratch: &'a mut Scratch,
+        ) -> Result<Waveform<'a>, AcceptWaveformError> {
+            Ok(Waveform::Pcm16(data))
+        }
+    }
+
+    impl Sealed for f32 {
+        fn waveform<'a>(
+            data: &'a [Self],
+            scratch: &'a mut Scratch,
+        ) -> Result<Waveform<'a>, AcceptWaveformError> {
+            Ok(scratch.float(data, |sample| sample * PCM16_SCALE))
+        }
+    }
+
+    impl Sealed for f64 {
+        fn waveform<'a>(
+            data: &'a [Self],
+            scratch: &'a mut Scratch,
+        ) -> Result<Waveform<'a>, AcceptWaveformError> {
+            Ok(scratch.float(data, |sample| sample as f32 * PCM16_SCALE))
+        }
+    }
+
+    impl Sealed for i32 {
+        fn waveform<'a>(
+            data: &'a [Self],
+            scratch: &'a mut Scratch,
+        ) -> Result<Waveform<'a>, AcceptWaveformError> {
+            // The shift leaves a value that always fits
+            Ok(scratch.pcm(data, |sample| (sample >> 16) as i16))
+        }
+    }
+
+    impl Sealed for u16 {
+        fn waveform<'a>(
+            data: &'a [Self],
+            scratch: &'a mut Scratch,
+        ) -> Result<Waveform<'a>, AcceptWaveformError> {
+            Ok(scratch.pcm(data, |sample| {
+                i16::from_ne_bytes((sample ^ 0x8000).to_ne_bytes())
+            }))
+        }
+    }
+
+    impl Sealed for u8 {
+        fn waveform<'a>(
+            data: &'a [Self],
+            scratch: &'a mut Scratch,
+        ) -> Result<Waveform<'a>, AcceptWaveformError> {
+            if data.len() % 2 == 1 {
+                return Err(AcceptWaveformError::OddByteCount(data.len()));
+            }
+
+            // Vosk reads the bytes as samples in the native byte order
+            if cfg!(target_endian = "big") {
+                Ok(scratch.pcm_from_le_bytes(data))
+            } else {
+                Ok(Waveform::Bytes(data))
+            }
+        }
+    }
+
+    impl Sealed for i8 {
+        fn waveform<'a>(
+            data: &'a [Self],
+            scratch: &'a mut Scratch,
+        ) -> Result<Waveform<'a>, AcceptWaveformError> {
+            // SAFETY: i8 and u8 have the same size and alignment, and every bit pattern
+            // is valid for both
+            let bytes =
+                unsafe { std::slice::from_raw_parts(data.as_ptr().cast::<u8>(), data.len()) };
+            u8::waveform(bytes, scratch)
+        }
+    }
+}
@@ -0,0 +1,127 @@
+use super::{
+    AcceptWaveformError, CompleteResult, CompleteResultSingle, DecodingState, PartialResult,
+    SpeechRecognizer,
+};
+use std::{borrow::Cow, collections::VecDeque};
+
+/// A [`SpeechRecognizer`] that returns scripted states and results instead of recognizing
+/// anything, to test code that is generic over [`SpeechRecognizer`] without a model.
+///
+/// States and results are returned in the order they were pushed. Once there are none left,
+/// [`accept_waveform`] returns [`DecodingState::Running`] and the results are empty.
+/// The audio it receives is recorded, and can be checked with [`accepted_samples`].
+///
+/// [`accept_waveform`]: SpeechRecognizer::accept_waveform
+/// [`accepted_samples`]: Self::accepted_samples
+#[derive(Debug, Clone, Default)]
+pub struct ScriptedRecognizer {
+    decoding_states: VecDeque<DecodingState>,
+    results: VecDeque<CompleteResult<'static>>,
+    partial_results: VecDeque<PartialResult<'static>>,
+    final_results: VecDeque<CompleteResult<'static>>,
+    accepted_samples: Vec<i16>,
+    resets: usize,
+}
+
+impl ScriptedRecognizer {
+    /// Creates a recognizer with nothing scripted.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a state to be returned by [`accept_waveform`](SpeechRecognizer::accept_waveform).
+    pub fn push_decoding_state(&mut self, state: DecodingState) {
+        self.decoding_states.push_back(state);
+    }
+
+    /// Adds a result to be returned by [`result`](SpeechRecognizer::result).
+    pub fn push_result(&mut self, result: CompleteResult<'static>) {
+        self.results.push_back(result);
+    }
+
+    /// Like [`push_result`](Self::push_result), with a [`CompleteResult::Single`]
+    /// that only has a text.
+    pub fn push_result_text(&mut self, text: impl Into<String>) {
+        self.push_result(text_result(text.into()));
+    }
+
+    /// Adds a result to be returned by [`partial_result`](SpeechRecognizer::partial_result).
+    pub fn push_partial_result(&mut self, result: PartialResult<'static>) {
+        self.partial_results.push_back(result);
+    }
+
+    /// Like [`push_partial_result`](Self::push_partial_result), with a result
+    /// that only has a text.
+    pub fn push_partial_result_text(&mut self, text: impl Into<String>) {
+        self.push_partial_result(PartialResult {
+            partial: Cow::Owned(text.into()),
+            partial_result: Vec::new(),
+        });
+    }
+
+    /// Adds a result to be returned by [`final_result`](SpeechRecognizer::final_result).
+    pub fn push_final_result(&mut self, result: CompleteResult<'static>) {
+        self.final_results.push_back(result);
+    }
+
+    /// Like [`push_final_result`](Self::push_final_result), with a [`CompleteResult::Single`]
+    /// that only has a text.
+    pub fn push_final_result_text(&mut self, text: impl Into<String>) {
+        self.push_final_result(text_result(text.into()));
+    }
+
+    /// All the samples passed to [`accept_waveform`](SpeechRecognizer::accept_waveform),
+    /// in order, including those before a [`reset`](SpeechRecognizer::reset).
+    #[must_use]
+    pub fn accepted_samples(&self) -> &[i16] {
+        &self.accepted_samples
+    }
+
+    /// How many times [`reset`](SpeechRecognizer::reset) was called.
+    #[must_use]
+    pub fn resets(&self) -> usize {
+        self.resets
+    }
+}
+
+fn text_result(text: String) -> CompleteResult<'static> {
+    CompleteResult::Single(CompleteResultSingle {
+        text: Cow::Owned(text),
+        ..CompleteResultSingle::default()
+    })
+}
+
+impl SpeechRecognizer for ScriptedRecognizer {
+    fn accept_waveform(&mut self, data: &[i16]) -> Result<DecodingState, AcceptWaveformError> {
+        if i32::try_from(data.len()).is_err() {
+            return Err(AcceptWaveformError::BufferTooLong(data.len()));
+        }
+
+        self.accepted_samples.extend_from_slice(data);
+        Ok(self
+            .decoding_states
+            .pop_front()
+            .unwrap_or(DecodingState::Running))
+    }
+
+    fn result(&mut self) -> CompleteResult<'static> {
+        self.results
+            .pop_front()
+            .unwrap_or_else(|| CompleteResult::Single(CompleteResultSingle::default()))
+    }
+
+    fn partial_result(&mut self) -> PartialResult<'static> {
+        self.partial_results.pop_front().unwrap_or_default()
+    }
+
+    fn final_result(&mut self) -> CompleteResult<'static> {
+        self.final_results
+            .pop_front()
+            .unwrap_or_else(|| CompleteResult::Single(CompleteResultSingle::default()))
+    }
+
+    fn reset(&mut self) {
+        self.resets += 1;
+    }
+}
@@ -1,63 +1,137 @@
 use super::{
-    result_from_json_c_str, AcceptWaveformError, CompleteResult, DecodingState, PartialResult,
+    channels::InputChannels,
+    complete_result_from_c_str, expect_result,
+    resampler::Resampler,
+    result_from_json_c_str,
+    samples::{Scratch, Waveform},
+    AcceptWaveformError, AudioSample, ChannelMix, CompleteResult, DecodingState, EndpointerDelays,
+    EndpointerMode, PartialResult, RecognizerError, ResultError,
+};
+use crate::{
+    grammar::{grammar_to_c_string, Grammar},
+    models::{GraphKind, Model, ModelRef, SpeakerModel, TextProcessor},
 };
-use crate::models::{Model, SpeakerModel};
 
-use std::{ffi::CString, os::raw::c_int, ptr::NonNull};
+use std::{
+    borrow::Cow,
+    os::raw::{c_char, c_int},
+    ptr::NonNull,
+};
 use vosk_sys::*;
 
 /// The main object which processes data.
 /// Takes audio as input and returns decoded information as words, confidences, times, and other metadata.
-pub struct Recognizer(NonNull<VoskRecognizer>);
+///
+/// A recognizer borrows the [`Model`] (and [`SpeakerModel`], if any) it was built from,
+/// so they cannot be dropped while it is still in use:
+///
+/// ```compile_fail,E0505
+/// # use vosk::{Model, Recognizer};
+/// let model = Model::new("/path/to/model").unwrap();
+/// let mut recognizer = Recognizer::new(&model, 16000.0).unwrap();
+/// drop(model);
+/// recognizer.accept_waveform(&[0; 100]).unwrap();
+/// ```
+///
+/// ```compile_fail,E0597
+/// # use vosk::{Model, Recognizer, SpeakerModel};
+/// let model = Model::new("/path/to/model").unwrap();
+/// let mut recognizer = Recognizer::new(&model, 16000.0).unwrap();
+/// {
+///     let speaker_model = SpeakerModel::new("/path/to/speaker/model").unwrap();
+///     recognizer.set_speaker_model(&speaker_model);
+/// }
+/// recognizer.accept_waveform(&[0; 100]).unwrap();
+/// ```
+///
+/// To get a `'static` recognizer that can be moved into another thread or task,
+/// create it from a [`SharedModel`] instead, which it will keep alive.
+///
+/// [`Model`]: crate::Model
+/// [`SpeakerModel`]: crate::SpeakerModel
+/// [`SharedModel`]: crate::SharedModel
+pub struct Recognizer<'m> {
+    ptr: NonNull<VoskRecognizer>,
+    model: ModelRef<'m, Model>,
+    speaker_model: Option<ModelRef<'m, SpeakerModel>>,
+    text_processor: Option<ModelRef<'m, TextProcessor>>,
+    /// Sample rate Vosk was given.
+    sample_rate: f32,
+    input_channels: InputChannels,
+    /// Converter from the rate set with [`Recognizer::set_input_rate`], if it differs.
+    resampler: Option<Resampler>,
+    /// Buffers for the samples Vosk cannot take directly.
+    scratch: Scratch,
+    /// Buffer for the mixed channels.
+    mixed: Vec<f32>,
+}
 
-impl Recognizer {
-    /// Creates the recognizer object. Returns [`None`] if a problem occured.
+impl<'m> Recognizer<'m> {
+    /// Sample rate of most Vosk models, used by [`with_input_rate`](Self::with_input_rate)
+    /// for models whose configuration does not specify one.
+    pub const DEFAULT_SAMPLE_RATE: f32 = 16000.0;
+
+    /// Creates the recognizer object.
     ///
     /// The recognizers process the speech and return text using shared model data.
     ///
     /// * `model` - [`Model`] containing static data for recognizer. Model can be shared
-    ///   across recognizers, even running in different threads.
+    ///   across recognizers, even running in different threads. Either a reference to
+    ///   a model or a [`SharedModel`], which the recognizer will keep alive.
     ///
     /// * `sample_rate` - The sample rate of the audio you going to feed into the recognizer.
     ///   Make sure this rate matches the audio content, it is a common issue causing accuracy problems.
+    ///   The rate the model was trained with can be checked with [`Model::info`], and
+    ///   [`with_input_rate`] creates a recognizer at that rate, resampling the audio.
     ///
+    /// [`with_input_rate`]: Self::with_input_rate
     /// [`Model`]: crate::Model
-    #[must_use]
-    pub fn new(model: &Model, sample_rate: f32) -> Option<Self> {
-        let recognizer_ptr = unsafe { vosk_recognizer_new(model.0.as_ptr(), sample_rate) };
-        Some(Self(NonNull::new(recognizer_ptr)?))
+    /// [`Model::info`]: crate::Model::info
+    /// [`SharedModel`]: crate::SharedModel
+    pub fn new(
+        model: impl Into<ModelRef<'m, Model>>,
+        sample_rate: f32,
+    ) -> Result<Self, RecognizerError> {
+        let model = model.into();
+        let recognizer_ptr = unsafe { vosk_recognizer_new(model.ptr.as_ptr(), sample_rate) };
+
+        Self::from_ptr(recognizer_ptr, model, None, sample_rate)
     }
 
-    /// Creates the recognizer object with speaker recognition. Returns [`None`] if a problem occured
+    /// Creates the recognizer object with speaker recognition.
     ///
     /// With the speaker recognition mode the recognizer not just recognize
     /// text but also return speaker vectors one can use for speaker identification
     ///
     /// * `model` - [`Model`] containing the data for recognizer. Model can be
-    ///   shared across recognizers, even running in different threads.
+    ///   shared across recognizers, even running in different threads. Either a reference to
+    ///   a model or a [`SharedModel`], which the recognizer will keep alive.
     ///
     /// * `sample_rate` - The sample rate of the audio you going to feed into the recognizer.
     ///   Make sure this rate matches the audio content, it is a common
     ///   issue causing accuracy problems.
     ///
-    /// * `spk_model` - Speaker model for speaker identification.
+    /// * `spk_model` - Speaker model for speaker identification. Either a reference to
+    ///   a speaker model or a [`SharedSpeakerModel`].
     ///
     /// [`Model`]: crate::Model
-    #[must_use]
+    /// [`SharedModel`]: crate::SharedModel
+    /// [`SharedSpeakerModel`]: crate::SharedSpeakerModel
     pub fn new_with_speaker(
-        model: &Model,
+        model: impl Into<ModelRef<'m, Model>>,
         sample_rate: f32,
-        speaker_model: &SpeakerModel,
-    ) -> Option<Self> {
+        speaker_model: impl Into<ModelRef<'m, SpeakerModel>>,
+    ) -> Result<Self, RecognizerError> {
+        let model = model.into();
+        let speaker_model = speaker_model.into();
         let recognizer_ptr = unsafe {
-            vosk_recognizer_new_spk(model.0.as_ptr(), sample_rate, speaker_model.0.as_ptr())
+            vosk_recognizer_new_spk(model.ptr.as_ptr(), sample_rate, speaker_model.0.as_ptr())
         };
 
-        Some(Self(NonNull::new(recognizer_ptr)?))
+        Self::from_ptr(recognizer_ptr, model, Some(speaker_model), sample_rate)
     }
 
     /// Creates the recognizer object with that only recognizes certain words.
-    /// Returns [`None`] if a problem occured.
     ///
     /// Sometimes when you want to improve recognition accuracy and when you don't need
     /// to recognize large vocabulary you can specify a list of phrases to recognize. This
@@ -65,15 +139,20 @@ impl Recognizer {
     /// something different.
     ///
     /// Only recognizers with lookahead models support this type of quick configuration.
-    /// Precompiled HCLG graph models are not supported.
+    /// Precompiled HCLG graph models are not supported, and
+    /// [`RecognizerError::GrammarNotSupported`] is returned for them.
     ///
     /// * `model` - [`Model`] containing the data for recognizer. Model can be shared
-    ///   across recognizers, even running in different threads.
+    ///   across recognizers, even running in different threads. Either a reference to
+    ///   a model or a [`SharedModel`], which the recognizer will keep alive.
     ///
     /// * `sample_rate` - The sample rate of the audio you going to feed into the recognizer.
     ///   Make sure this rate matches the audio content, it is a common issue causing accuracy problems.
     ///
-    /// * `grammar` - The list of phrases to recognize.
+    /// * `grammar` - The list of phrases to recognize, either as a [`Grammar`] or a
+    ///   list of strings. They are encoded with [`grammar_to_json`], so phrases with
+    ///   control characters are rejected. Use [`Grammar::validate`] to make sure that
+    ///   all the words in the phrases can be recognized by the model.
     ///
     /// # Examples
     ///
@@ -90,33 +169,183 @@ impl Recognizer {
     /// ```
     ///
     /// [`Model`]: crate::Model
-    #[must_use]
+    /// [`SharedModel`]: crate::SharedModel
+    /// [`Grammar`]: crate::Grammar
+    /// [`Grammar::validate`]: crate::Grammar::validate
+    /// [`grammar_to_json`]: crate::grammar_to_json
     pub fn new_with_grammar(
-        model: &Model,
+        model: impl Into<ModelRef<'m, Model>>,
         sample_rate: f32,
-        grammar: &[impl AsRef<str>],
-    ) -> Option<Self> {
-        let grammar_c = CString::new(format!(
-            "[{}]",
-            grammar
-                .iter()
-                .map(|phrase| format!("\"{}\"", phrase.as_ref()))
-                .collect::<Vec<_>>()
-                .join(", ")
-        ))
-        .ok()?;
+        grammar: impl Into<Grammar>,
+    ) -> Result<Self, RecognizerError> {
+        let model = model.into();
+        if model.graph() != GraphKind::Lookahead {
+            return Err(RecognizerError::GrammarNotSupported(
+                model.path().to_owned(),
+            ));
+        }
+
+        let grammar_c = grammar_to_c_string(grammar.into().phrases())?;
         let recognizer_ptr =
-            unsafe { vosk_recognizer_new_grm(model.0.as_ptr(), sample_rate, grammar_c.as_ptr()) };
+            unsafe { vosk_recognizer_new_grm(model.ptr.as_ptr(), sample_rate, grammar_c.as_ptr()) };
 
-        Some(Self(NonNull::new(recognizer_ptr)?))
+        Self::from_ptr(recognizer_ptr, model, None, sample_rate)
+    }
+
+    /// Wraps a recognizer created by Vosk, or returns [`RecognizerError::CreationFailed`]
+    /// if `ptr` is null.
+    fn from_ptr(
+        ptr: *mut VoskRecognizer,
+        model: ModelRef<'m, Model>,
+        speaker_model: Option<ModelRef<'m, SpeakerModel>>,
+        sample_rate: f32,
+    ) -> Result<Self, RecognizerError> {
+        Ok(Self {
+            ptr: NonNull::new(ptr).ok_or(RecognizerError::CreationFailed)?,
+            model,
+            speaker_model,
+            text_processor: None,
+            sample_rate,
+            input_channels: InputChannels::default(),
+            resampler: None,
+            scratch: Scratch::default(),
+            mixed: Vec::new(),
+        })
+    }
+
+    /// Creates a recognizer at the sample rate the model was trained with, that takes audio
+    /// at `input_rate` and resamples it (see [`set_input_rate`]).
+    ///
+    /// The rate of the model is read from its configuration (see [`Model::info`]), or is
+    /// [`DEFAULT_SAMPLE_RATE`] if it does not specify one.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use vosk::{Model, Recognizer};
+    /// #
+    /// let model = Model::new("/path/to/model").expect("Could not create a model");
+    ///
+    /// // Audio from a sound card, for a model trained on 16 kHz audio
+    /// let mut recognizer =
+    ///     Recognizer::with_input_rate(&model, 48000.0).expect("Could not create a recognizer");
+    ///
+    /// # let samples: Vec<f32> = Vec::new();
+    /// recognizer.accept_samples(&samples).unwrap();
+    /// ```
+    ///
+    /// [`set_input_rate`]: Self::set_input_rate
+    /// [`Model::info`]: crate::Model::info
+    /// [`DEFAULT_SAMPLE_RATE`]: Self::DEFAULT_SAMPLE_RATE
+    pub fn with_input_rate(
+        model: impl Into<ModelRef<'m, Model>>,
+        input_rate: f32,
+    ) -> Result<Self, RecognizerError> {
+        let model = model.into();
+        let model_rate = Self::model_rate(&model);
+
+        let mut recognizer = Self::new(model, model_rate)?;
+        recognizer.set_input_rate(input_rate)?;
+        Ok(recognizer)
+    }
+
+    /// Sample rate `model` was trained on, or [`DEFAULT_SAMPLE_RATE`](Self::DEFAULT_SAMPLE_RATE)
+    /// if it cannot be read from its configuration.
+    pub(crate) fn model_rate(model: &Model) -> f32 {
+        model
+            .info()
+            .ok()
+            .and_then(|info| info.sample_frequency)
+            .unwrap_or(Self::DEFAULT_SAMPLE_RATE)
     }
 
     /// Adds speaker model to already initialized recognizer
     ///
     /// Can add speaker recognition model to already created recognizer. Helps to initialize
     /// speaker recognition for grammar-based recognizer.
-    pub fn set_speaker_model(&mut self, speaker_model: &SpeakerModel) {
-        unsafe { vosk_recognizer_set_spk_model(self.0.as_ptr(), speaker_model.0.as_ptr()) }
+    ///
+    /// * `speaker_model` - Either a reference to a [`SpeakerModel`] or a [`SharedSpeakerModel`],
+    ///   which the recognizer will keep alive.
+    ///
+    /// [`SpeakerModel`]: crate::SpeakerModel
+    /// [`SharedSpeakerModel`]: crate::SharedSpeakerModel
+    pub fn set_speaker_model(&mut self, speaker_model: impl Into<ModelRef<'m, SpeakerModel>>) {
+        let speaker_model = speaker_model.into();
+        unsafe { vosk_recognizer_set_spk_model(self.ptr.as_ptr(), speaker_model.0.as_ptr()) }
+        self.speaker_model = Some(speaker_model);
+    }
+
+    /// Replaces the phrases the recognizer is limited to, without creating a new recognizer.
+    ///
+    /// This works on any recognizer whose model has a lookahead graph, whether it was created
+    /// with a grammar or not. An empty grammar removes the limit, so the whole vocabulary
+    /// of the model can be recognized again. Audio that was accepted but not returned in a
+    /// result yet is discarded, like with [`reset`].
+    ///
+    /// Returns [`RecognizerError::GrammarNotSupported`] if the model only has a static graph,
+    /// and [`RecognizerError::Grammar`] if the grammar cannot be encoded (see [`grammar_to_json`]).
+    /// The recognizer is left unchanged in both cases.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use vosk::{Grammar, Model, Recognizer};
+    /// #
+    /// let model = Model::new("/path/to/model").expect("Could not create a model");
+    /// let mut recognizer = Recognizer::new(&model, 16000.0).expect("Could not create a recognizer");
+    ///
+    /// let grammar = Grammar::new().phrase("yes").phrase("no").with_unknown();
+    /// recognizer.set_grammar(&grammar).expect("Could not set the grammar");
+    /// ```
+    ///
+    /// [`reset`]: Self::reset
+    /// [`grammar_to_json`]: crate::grammar_to_json
+    pub fn set_grammar(&mut self, grammar: &Grammar) -> Result<(), RecognizerError> {
+        if self.model.graph() != GraphKind::Lookahead {
+            return Err(RecognizerError::GrammarNotSupported(
+                self.model.path().to_owned(),
+            ));
+        }
+
+        let grammar_c = grammar_to_c_string(grammar.phrases())?;
+
+        // Vosk refuses to change the grammar in the middle of an utterance
+        self.reset();
+        unsafe { vosk_recognizer_set_grm(self.ptr.as_ptr(), grammar_c.as_ptr()) };
+
+        Ok(())
+    }
+
+    /// Sets a [`TextProcessor`] that converts the text of the results returned by [`result`]
+    /// and [`final_result`] to its written form: [`CompleteResultSingle::text`],
+    /// the [`Alternative::text`] of every alternative and the [`NlsmlInterpretation::input`]
+    /// of every interpretation.
+    ///
+    /// Partial results, the words in [`CompleteResultSingle::result`] and
+    /// [`Alternative::result`], and the XML of NLSML results are left unchanged.
+    ///
+    /// * `text_processor` - Either a reference to a [`TextProcessor`] or an
+    ///   [`Arc<TextProcessor>`](std::sync::Arc), which the recognizer will keep alive.
+    ///
+    /// [`TextProcessor`]: crate::TextProcessor
+    /// [`result`]: Self::result
+    /// [`final_result`]: Self::final_result
+    /// [`CompleteResultSingle::text`]: crate::CompleteResultSingle::text
+    /// [`CompleteResultSingle::result`]: crate::CompleteResultSingle::result
+    /// [`Alternative::text`]: crate::Alternative::text
+    /// [`Alternative::result`]: crate::Alternative::result
+    /// [`NlsmlInterpretation::input`]: crate::NlsmlInterpretation::input
+    pub fn set_text_processor(&mut self, text_processor: impl Into<ModelRef<'m, TextProcessor>>) {
+        self.text_processor = Some(text_processor.into());
+    }
+
+    /// Removes the [`TextProcessor`] set with [`set_text_processor`], so the text of the results
+    /// is returned as Vosk recognized it.
+    ///
+    /// [`TextProcessor`]: crate::TextProcessor
+    /// [`set_text_processor`]: Self::set_text_processor
+    pub fn remove_text_processor(&mut self) {
+        self.text_processor = None;
     }
 
     /// Configures recognizer to output n-best results in [`result`] and [`final_result`]
@@ -131,7 +360,9 @@ impl Recognizer {
     /// [`CompleteResult::Single`]: crate::CompleteResult::Single
     /// [`CompleteResult::Multiple`]: crate::CompleteResult::Multiple
     pub fn set_max_alternatives(&mut self, max_alternatives: u16) {
-        unsafe { vosk_recognizer_set_max_alternatives(self.0.as_ptr(), max_alternatives as c_int) }
+        unsafe {
+            vosk_recognizer_set_max_alternatives(self.ptr.as_ptr(), max_alternatives as c_int)
+        }
     }
 
     /// Enables or disables words with metadata in the output, represented as:
@@ -145,7 +376,7 @@ impl Recognizer {
     /// [`CompleteResult::Multiple`]: crate::CompleteResult::Multiple
     /// [`CompleteResult::Single`]: crate::CompleteResult::Single
     pub fn set_words(&mut self, enable: bool) {
-        unsafe { vosk_recognizer_set_words(self.0.as_ptr(), i32::from(enable)) }
+        unsafe { vosk_recognizer_set_words(self.ptr.as_ptr(), i32::from(enable)) }
     }
 
     /// Like [`set_words`], but for [`PartialResult`].
@@ -156,34 +387,188 @@ impl Recognizer {
     /// [`PartialResult`]: crate::PartialResult
     /// [`Word`]: crate::Word
     pub fn set_partial_words(&mut self, enable: bool) {
-        unsafe { vosk_recognizer_set_partial_words(self.0.as_ptr(), i32::from(enable)) }
+        unsafe { vosk_recognizer_set_partial_words(self.ptr.as_ptr(), i32::from(enable)) }
     }
 
     /// Enables or disables Natural Language Semantics Markup Language (NLSML) in the output
+    ///
+    /// When enabled, [`result`] and [`final_result`] return a [`CompleteResult::Nlsml`].
+    /// Partial results are not affected.
+    ///
+    /// [`result`]: Self::result
+    /// [`final_result`]: Self::final_result
+    /// [`CompleteResult::Nlsml`]: crate::CompleteResult::Nlsml
     pub fn set_nlsml(&mut self, enable: bool) {
-        unsafe { vosk_recognizer_set_nlsml(self.0.as_ptr(), i32::from(enable)) }
+        unsafe { vosk_recognizer_set_nlsml(self.ptr.as_ptr(), i32::from(enable)) }
+    }
+
+    /// Sets how long the pauses that end an utterance are, relative to the endpoint rules
+    /// of the model.
+    ///
+    /// Default: [`EndpointerMode::Default`].
+    ///
+    /// [`EndpointerMode::Default`]: crate::EndpointerMode::Default
+    pub fn set_endpointer_mode(&mut self, mode: EndpointerMode) {
+        unsafe { vosk_recognizer_set_endpointer_mode(self.ptr.as_ptr(), mode.to_c()) }
+    }
+
+    /// Sets the timeouts that end an utterance, overriding the endpoint rules of the model.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::time::Duration;
+    /// # use vosk::{EndpointerDelays, Model, Recognizer};
+    /// #
+    /// let model = Model::new("/path/to/model").expect("Could not create a model");
+    /// let mut recognizer = Recognizer::new(&model, 16000.0).expect("Could not create a recognizer");
+    ///
+    /// recognizer.set_endpointer_delays(EndpointerDelays {
+    ///     initial_silence: Duration::from_secs(5),
+    ///     trailing_silence: Duration::from_millis(300),
+    ///     max_utterance: Duration::from_secs(10),
+    /// });
+    /// ```
+    pub fn set_endpointer_delays(&mut self, delays: EndpointerDelays) {
+        unsafe {
+            vosk_recognizer_set_endpointer_delays(
+                self.ptr.as_ptr(),
+                delays.initial_silence.as_secs_f32(),
+                delays.trailing_silence.as_secs_f32(),
+                delays.max_utterance.as_secs_f32(),
+            )
+        }
+    }
+
+    /// Sets the number of channels of the audio passed to [`accept_waveform`] and
+    /// [`accept_samples`], whose samples are interleaved (one sample of each channel,
+    /// then the next sample of each channel...), and how they are turned into the single
+    /// channel Vosk recognizes.
+    ///
+    /// Default: 1 channel.
+    ///
+    /// Returns [`RecognizerError::InvalidChannels`] if `channels` is 0, or `mix` selects
+    /// a channel that does not exist. The layout is left unchanged in that case.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use vosk::{ChannelMix, Model, Recognizer};
+    /// #
+    /// let model = Model::new("/path/to/model").expect("Could not create a model");
+    /// let mut recognizer = Recognizer::new(&model, 8000.0).expect("Could not create a recognizer");
+    ///
+    /// // Recognize what the customer, on the right channel of a call recording, says
+    /// recognizer
+    ///     .set_input_channels(2, ChannelMix::Channel(1))
+    ///     .expect("Invalid channels");
+    ///
+    /// # let stereo_samples: Vec<i16> = Vec::new();
+    /// recognizer.accept_waveform(&stereo_samples).unwrap();
+    /// ```
+    ///
+    /// [`accept_waveform`]: Self::accept_waveform
+    /// [`accept_samples`]: Self::accept_samples
+    pub fn set_input_channels(
+        &mut self,
+        channels: u16,
+        mix: ChannelMix,
+    ) -> Result<(), RecognizerError> {
+        self.input_channels = InputChannels::new(channels, mix)
+            .ok_or(RecognizerError::InvalidChannels { channels, mix })?;
+        Ok(())
+    }
+
+    /// Sets the sample rate of the audio passed to [`accept_waveform`] and [`accept_samples`],
+    /// which is resampled to the rate the recognizer was created with if they differ.
+    ///
+    /// The resampler keeps its state between chunks, so audio can be passed in chunks of any
+    /// size. It delays the audio by a few samples, which are recognized once the next chunk
+    /// arrives, or by [`final_result`], which flushes them. [`reset`] discards them.
+    ///
+    /// Default: the rate the recognizer was created with.
+    ///
+    /// Returns [`RecognizerError::InvalidSampleRate`] if `input_rate` is not a positive number.
+    ///
+    /// [`accept_waveform`]: Self::accept_waveform
+    /// [`accept_samples`]: Self::accept_samples
+    /// [`final_result`]: Self::final_result
+    /// [`reset`]: Self::reset
+    pub fn set_input_rate(&mut self, input_rate: f32) -> Result<(), RecognizerError> {
+        if !(input_rate.is_finite() && input_rate > 0.0) {
+            return Err(RecognizerError::InvalidSampleRate(input_rate));
+        }
+
+        self.resampler =
+            (input_rate != self.sample_rate).then(|| Resampler::new(input_rate, self.sample_rate));
+        Ok(())
     }
 
     /// Accept and process new chunk of voice data.
     ///
-    /// * `data` - Audio data in PCM 16-bit mono format.
+    /// * `data` - Audio data in the PCM 16-bit format. Mono, unless other channels were set
+    ///   with [`set_input_channels`], and at the sample rate the recognizer was created with,
+    ///   unless another one was set with [`set_input_rate`].
     ///
     /// Returns a [`DecodingState`], which represents the state of the decodification
     /// after this chunk of data has been processed.
+    ///
+    /// See [`accept_samples`] for audio in other formats.
+    ///
+    /// [`set_input_channels`]: Self::set_input_channels
+    /// [`set_input_rate`]: Self::set_input_rate
+    /// [`accept_samples`]: Self::accept_samples
     pub fn accept_waveform(&mut self, data: &[i16]) -> Result<DecodingState, AcceptWaveformError> {
-        // vosk_recognizer_accept_waveform and vosk_recognizer_accept_waveform_f are meant
-        // to be used by languages that do not have an i16 type (those functions also take PCM 16-bit audio,
-        // but represented as an f32 or i8). Those extra functions aren't needed in rust so they
-        // will not be wrapped
-
-        let len = data.len();
-
-        let decoding_state = unsafe {
-            vosk_recognizer_accept_waveform_s(
-                self.0.as_ptr(),
-                data.as_ptr(),
-                i32::try_from(len).map_err(|_| AcceptWaveformError::BufferTooLong(len))?,
-            )
+        self.accept_samples(data)
+    }
+
+    /// Like [`accept_waveform`], but with audio in any of the formats listed in [`AudioSample`],
+    /// such as floating point samples ([`f32`]) or the bytes of 16-bit little-endian PCM ([`u8`]).
+    ///
+    /// The type of the samples selects how they are read, so integer literals, which default
+    /// to [`i32`], should be given a type.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use vosk::{Model, Recognizer};
+    /// #
+    /// let model = Model::new("/path/to/model").expect("Could not create a model");
+    /// let mut recognizer = Recognizer::new(&model, 16000.0).expect("Could not create a recognizer");
+    ///
+    /// // Samples from an audio library
+    /// let samples: Vec<f32> = vec![0.0; 1600];
+    /// recognizer.accept_samples(&samples).unwrap();
+    ///
+    /// // Bytes of 16-bit little-endian PCM, such as from the network
+    /// let bytes: Vec<u8> = vec![0; 3200];
+    /// recognizer.accept_samples(&bytes).unwrap();
+    /// ```
+    ///
+    /// [`accept_waveform`]: Self::accept_waveform
+    /// [`AudioSample`]: crate::AudioSample
+    pub fn accept_samples<S: AudioSample>(
+        &mut self,
+        data: &[S],
+    ) -> Result<DecodingState, AcceptWaveformError> {
+        let ptr = self.ptr.as_ptr();
+
+        let waveform = S::waveform(data, &mut self.scratch)?;
+        let mut waveform = self.input_channels.mix(waveform, &mut self.mixed)?;
+        if let Some(resampler) = &mut self.resampler {
+            waveform = resampler.process(waveform);
+        }
+
+        let decoding_state = match waveform {
+            Waveform::Pcm16(samples) => unsafe {
+                vosk_recognizer_accept_waveform_s(ptr, samples.as_ptr(), c_len(samples.len())?)
+            },
+            Waveform::Float(samples) => unsafe {
+                vosk_recognizer_accept_waveform_f(ptr, samples.as_ptr(), c_len(samples.len())?)
+            },
+            Waveform::Bytes(bytes) => unsafe {
+                vosk_recognizer_accept_waveform(ptr, bytes.as_ptr().cast(), c_len(bytes.len())?)
+            },
         };
 
         Ok(DecodingState::from_c_int(decoding_state))
@@ -193,17 +578,34 @@ impl Recognizer {
     ///
     /// The returned value will be a [`CompleteResult::Single`]
     /// if [`set_max_alternatives`] was passed a 0 (default), and
-    /// [`CompleteResult::Multiple`] otherwise.
+    /// [`CompleteResult::Multiple`] otherwise, unless NLSML is enabled
+    /// (see [`set_nlsml`]).
     ///
     /// If words are enabled (see [`set_words`]), it also returns metadata about the words.
     ///
+    /// # Panics
+    ///
+    /// Panics if Vosk returns a result this crate cannot parse. Use [`try_result`] to handle
+    /// that case instead.
+    ///
     /// [`set_max_alternatives`]: Self::set_max_alternatives
     /// [`set_words`]: Self::set_words
+    /// [`set_nlsml`]: Self::set_nlsml
+    /// [`try_result`]: Self::try_result
     /// [`CompleteResult::Multiple`]: crate::CompleteResult::Multiple
     /// [`CompleteResult::Single`]: crate::CompleteResult::Single
     #[must_use]
-    pub fn result(&mut self) -> CompleteResult {
-        unsafe { result_from_json_c_str(vosk_recognizer_result(self.0.as_ptr())) }
+    pub fn result(&mut self) -> CompleteResult<'static> {
+        expect_result(self.try_result())
+    }
+
+    /// Like [`result`], but returns an error instead of panicking if Vosk returns a result
+    /// this crate cannot parse.
+    ///
+    /// [`result`]: Self::result
+    pub fn try_result(&mut self) -> Result<CompleteResult<'static>, ResultError> {
+        let result_ptr = unsafe { vosk_recognizer_result(self.ptr.as_ptr()) };
+        unsafe { self.complete_result(result_ptr) }
     }
 
     /// Returns partial speech recognition, which is not yet finalized and may change after
@@ -211,37 +613,123 @@ impl Recognizer {
     ///
     /// If words are enabled (see [`set_partial_words`]), it also returns metadata about the words.
     ///
+    /// # Panics
+    ///
+    /// Panics if Vosk returns a result this crate cannot parse. Use [`try_partial_result`]
+    /// to handle that case instead.
+    ///
     /// [`set_partial_words`]: Self::set_partial_words
+    /// [`try_partial_result`]: Self::try_partial_result
     #[must_use]
-    pub fn partial_result(&mut self) -> PartialResult {
-        unsafe { result_from_json_c_str(vosk_recognizer_partial_result(self.0.as_ptr())) }
+    pub fn partial_result(&mut self) -> PartialResult<'static> {
+        expect_result(self.try_partial_result())
+    }
+
+    /// Like [`partial_result`], but returns an error instead of panicking if Vosk returns
+    /// a result this crate cannot parse.
+    ///
+    /// [`partial_result`]: Self::partial_result
+    pub fn try_partial_result(&mut self) -> Result<PartialResult<'static>, ResultError> {
+        unsafe { result_from_json_c_str(vosk_recognizer_partial_result(self.ptr.as_ptr())) }
     }
 
     /// Returns speech recognition result. Like [`result`] but it does not
     /// wait for silence and it flushes the data so everything is processed
     ///
+    /// This includes the audio still held by the resampler (see [`set_input_rate`]).
+    ///
+    /// # Panics
+    ///
+    /// Panics if Vosk returns a result this crate cannot parse. Use [`try_final_result`]
+    /// to handle that case instead.
+    ///
     /// [`result`]: Self::result
+    /// [`set_input_rate`]: Self::set_input_rate
+    /// [`try_final_result`]: Self::try_final_result
     #[must_use]
-    pub fn final_result(&mut self) -> CompleteResult {
-        unsafe { result_from_json_c_str(vosk_recognizer_final_result(self.0.as_ptr())) }
+    pub fn final_result(&mut self) -> CompleteResult<'static> {
+        expect_result(self.try_final_result())
+    }
+
+    /// Like [`final_result`], but returns an error instead of panicking if Vosk returns
+    /// a result this crate cannot parse.
+    ///
+    /// [`final_result`]: Self::final_result
+    pub fn try_final_result(&mut self) -> Result<CompleteResult<'static>, ResultError> {
+        if let Some(resampler) = &mut self.resampler {
+            let tail = resampler.flush();
+            // The tail is at most the width of the filter of the resampler, so its length
+            // fits in a c_int
+            if !tail.is_empty() {
+                unsafe {
+                    vosk_recognizer_accept_waveform_f(
+                        self.ptr.as_ptr(),
+                        tail.as_ptr(),
+                        tail.len() as c_int,
+                    );
+                }
+            }
+        }
+
+        let result_ptr = unsafe { vosk_recognizer_final_result(self.ptr.as_ptr()) };
+        unsafe { self.complete_result(result_ptr) }
     }
 
     /// Resets current results and data so the recognition can continue from scratch
     pub fn reset(&mut self) {
         unsafe {
-            vosk_recognizer_reset(self.0.as_ptr());
+            vosk_recognizer_reset(self.ptr.as_ptr());
+        }
+
+        if let Some(resampler) = &mut self.resampler {
+            resampler.reset();
         }
     }
+
+    /// Parses a complete result returned by Vosk and applies the text processor to it, if any.
+    unsafe fn complete_result(
+        &self,
+        ptr: *const c_char,
+    ) -> Result<CompleteResult<'static>, ResultError> {
+        let mut result = complete_result_from_c_str(ptr)?;
+
+        if let Some(text_processor) = &self.text_processor {
+            match &mut result {
+                CompleteResult::Single(single) => {
+                    single.text = Cow::Owned(text_processor.itn(&single.text));
+                }
+                CompleteResult::Multiple(multiple) => {
+                    for alternative in &mut multiple.alternatives {
+                        alternative.text = Cow::Owned(text_processor.itn(&alternative.text));
+                    }
+                }
+                CompleteResult::Nlsml(nlsml) => {
+                    for interpretation in &mut nlsml.interpretations {
+                        if let Some(input) = &mut interpretation.input {
+                            *input = text_processor.itn(input);
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(result)
+    }
+}
+
+/// Converts the length of a buffer for Vosk.
+fn c_len(len: usize) -> Result<c_int, AcceptWaveformError> {
+    c_int::try_from(len).map_err(|_| AcceptWaveformError::BufferTooLong(len))
 }
 
 // SAFETY: Recognizer shares no state, so it is Send
-unsafe impl Send for Recognizer {}
+unsafe impl Send for Recognizer<'_> {}
 // SAFETY: All methods that mutate Recognizer require a &mut to it,
 // which ensures exclusive access, so it is Sync
-unsafe impl Sync for Recognizer {}
+unsafe impl Sync for Recognizer<'_> {}
 
-impl Drop for Recognizer {
+impl Drop for Recognizer<'_> {
     fn drop(&mut self) {
-        unsafe { vosk_recognizer_free(self.0.as_ptr()) }
+        unsafe { vosk_recognizer_free(self.ptr.as_ptr()) }
     }
 }
@@ -0,0 +1,239 @@
+use super::{
+    AcceptWaveformError, CompleteResult, DecodingState, PartialResult, Recognizer, ResultError,
+};
+
+/// Maximum number of samples the default [`SpeechRecognizer::accept_waveform_f32`]
+/// converts at once.
+const F32_CHUNK_LEN: usize = 4096;
+
+/// Common interface of the speech recognizers: a [`Recognizer`], a [`ScriptedRecognizer`]
+/// in tests, or another engine.
+///
+/// Code that only feeds audio and reads results can be generic over it, so a different
+/// implementation can be substituted. Results are owned, so they can be kept after the next call.
+///
+/// # Examples
+///
+/// ```
+/// use vosk::{DecodingState, ScriptedRecognizer, SpeechRecognizer};
+///
+/// /// Returns the text of every utterance in `audio`.
+/// fn transcribe(recognizer: &mut impl SpeechRecognizer, audio: &[i16]) -> Vec<String> {
+///     let mut texts = Vec::new();
+///
+///     for chunk in audio.chunks(100) {
+///         if recognizer.accept_waveform(chunk).unwrap() == DecodingState::Finalized {
+///             texts.extend(recognizer.result().single().map(|result| result.text.into_owned()));
+///         }
+///     }
+///
+///     texts.extend(recognizer.final_result().single().map(|result| result.text.into_owned()));
+///     texts
+/// }
+///
+/// let mut recognizer = ScriptedRecognizer::new();
+/// recognizer.push_decoding_state(DecodingState::Finalized);
+/// recognizer.push_result_text("hello");
+/// recognizer.push_final_result_text("world");
+///
+/// assert_eq!(transcribe(&mut recognizer, &[0; 150]), ["hello", "world"]);
+/// ```
+///
+/// [`ScriptedRecognizer`]: crate::ScriptedRecognizer
+pub trait SpeechRecognizer {
+    /// Accepts and processes a new chunk of audio, in PCM 16-bit mono format.
+    ///
+    /// See [`Recognizer::accept_waveform`].
+    fn accept_waveform(&mut self, data: &[i16]) -> Result<DecodingState, AcceptWaveformError>;
+
+    /// Accepts and processes a new chunk of audio, in floating point mono format, with samples
+    /// between `-1.0` and `1.0`.
+    ///
+    /// By default, the samples are converted to 16-bit PCM on the stack and passed to
+    /// [`accept_waveform`](Self::accept_waveform), in as many calls as needed for
+    /// 4096 samples at most each. It stops at the first
+    /// call that returns [`DecodingState::Failed`] or an error, and otherwise returns
+    /// [`DecodingState::Finalized`] if any of them did. See [`Recognizer::accept_waveform`].
+    fn accept_waveform_f32(&mut self, data: &[f32]) -> Result<DecodingState, AcceptWaveformError> {
+        let mut buffer = [0; F32_CHUNK_LEN];
+        let mut state = DecodingState::Running;
+
+        for chunk in data.chunks(F32_CHUNK_LEN) {
+            let samples = &mut buffer[..chunk.len()];
+            for (sample, &float) in samples.iter_mut().zip(chunk) {
+                // The cast saturates samples out of range
+                *sample = (float * 32768.0) as i16;
+            }
+
+            match self.accept_waveform(samples)? {
+                DecodingState::Running => {}
+                DecodingState::Finalized => state = DecodingState::Finalized,
+                DecodingState::Failed => return Ok(DecodingState::Failed),
+            }
+        }
+
+        Ok(state)
+    }
+
+    /// Returns the result of the utterance that just ended.
+    ///
+    /// See [`Recognizer::result`].
+    fn result(&mut self) -> CompleteResult<'static>;
+
+    /// Returns the partial result of the current utterance, which may change after
+    /// processing more data.
+    ///
+    /// See [`Recognizer::partial_result`].
+    fn partial_result(&mut self) -> PartialResult<'static>;
+
+    /// Processes the remaining audio and returns its result, without waiting for silence.
+    ///
+    /// See [`Recognizer::final_result`].
+    fn final_result(&mut self) -> CompleteResult<'static>;
+
+    /// Like [`result`](Self::result), but returns an error instead of panicking if the result
+    /// cannot be parsed.
+    ///
+    /// By default, it returns the result of [`result`](Self::result).
+    /// See [`Recognizer::try_result`].
+    fn try_result(&mut self) -> Result<CompleteResult<'static>, ResultError> {
+        Ok(self.result())
+    }
+
+    /// Like [`partial_result`](Self::partial_result), but returns an error instead of panicking
+    /// if the result cannot be parsed.
+    ///
+    /// By default, it returns the result of [`partial_result`](Self::partial_result).
+    /// See [`Recognizer::try_partial_result`].
+    fn try_partial_result(&mut self) -> Result<PartialResult<'static>, ResultError> {
+        Ok(self.partial_result())
+    }
+
+    /// Like [`final_result`](Self::final_result), but returns an error instead of panicking
+    /// if the result cannot be parsed.
+    ///
+    /// By default, it returns the result of [`final_result`](Self::final_result).
+    /// See [`Recognizer::try_final_result`].
+    fn try_final_result(&mut self) -> Result<CompleteResult<'static>, ResultError> {
+        Ok(self.final_result())
+    }
+
+    /// Discards the audio and results that were not returned yet.
+    ///
+    /// See [`Recognizer::reset`].
+    fn reset(&mut self);
+}
+
+impl SpeechRecognizer for Recognizer<'_> {
+    fn accept_waveform(&mut self, data: &[i16]) -> Result<DecodingState, AcceptWaveformError> {
+        Recognizer::accept_waveform(self, data)
+    }
+
+    fn accept_waveform_f32(&mut self, data: &[f32]) -> Result<DecodingState, AcceptWaveformError> {
+        Recognizer::accept_samples(self, data)
+    }
+
+    fn result(&mut self) -> CompleteResult<'static> {
+        Recognizer::result(self)
+    }
+
+    fn partial_result(&mut self) -> PartialResult<'static> {
+        Recognizer::partial_result(self)
+    }
+
+    fn final_result(&mut self) -> CompleteResult<'static> {
+        Recognizer::final_result(self)
+    }
+
+    fn try_result(&mut self) -> Result<CompleteResult<'static>, ResultError> {
+        Recognizer::try_result(self)
+    }
+
+    fn try_partial_result(&mut self) -> Result<PartialResult<'static>, ResultError> {
+        Recognizer::try_partial_result(self)
+    }
+
+    fn try_final_result(&mut self) -> Result<CompleteResult<'static>, ResultError> {
+        Recognizer::try_final_result(self)
+    }
+
+    fn reset(&mut self) {
+        Recognizer::reset(self);
+    }
+}
+
+impl<R: SpeechRecognizer + ?Sized> SpeechRecognizer for &mut R {
+    fn accept_waveform(&mut self, data: &[i16]) -> Result<DecodingState, AcceptWaveformError> {
+        (**self).accept_waveform(data)
+    }
+
+    fn accept_waveform_f32(&mut self, data: &[f32]) -> Result<DecodingState, AcceptWaveformError> {
+        (**self).accept_waveform_f32(data)
+    }
+
+    fn result(&mut self) -> CompleteResult<'static> {
+        (**self).result()
+    }
+
+    fn partial_result(&mut self) -> PartialResult<'static> {
+        (**self).partial_result()
+    }
+
+    fn final_result(&mut self) -> CompleteResult<'static> {
+        (**self).final_result()
+    }
+
+    fn try_result(&mut self) -> Result<CompleteResult<'static>, ResultError> {
+        (**self).try_result()
+    }
+
+    fn try_partial_result(&mut self) -> Result<PartialResult<'static>, ResultError> {
+        (**self).try_partial_result()
+    }
+
+    fn try_final_result(&mut self) -> Result<CompleteResult<'static>, ResultError> {
+        (**self).try_final_result()
+    }
+
+    fn reset(&mut self) {
+        (**self).reset();
+    }
+}
+
+impl<R: SpeechRecognizer + ?Sized> SpeechRecognizer for Box<R> {
+    fn accept_waveform(&mut self, data: &[i16]) -> Result<DecodingState, AcceptWaveformError> {
+        (**self).accept_waveform(data)
+    }
+
+    fn accept_waveform_f32(&mut self, data: &[f32]) -> Result<DecodingState, AcceptWaveformError> {
+        (**self).accept_waveform_f32(data)
+    }
+
+    fn result(&mut self) -> CompleteResult<'static> {
+        (**self).result()
+    }
+
+    fn partial_result(&mut self) -> PartialResult<'static> {
+        (**self).partial_result()
+    }
+
+    fn final_result(&mut self) -> CompleteResult<'static> {
+        (**self).final_result()
+    }
+
+    fn try_result(&mut self) -> Result<CompleteResult<'static>, ResultError> {
+        (**self).try_result()
+    }
+
+    fn try_partial_result(&mut self) -> Result<PartialResult<'static>, ResultError> {
+        (**self).try_partial_result()
+    }
+
+    fn try_final_result(&mut self) -> Result<CompleteResult<'static>, ResultError> {
+        (**self).try_final_result()
+    }
+
+    fn reset(&mut self) {
+        (**self).reset();
+    }
+}
@@ -0,0 +1,166 @@
+use super::{
+    AcceptWaveformError, CompleteResult, DecodingState, PartialResult, ResultError,
+    SpeechRecognizer,
+};
+use std::iter::FusedIterator;
+use thiserror::Error;
+
+/// Number of samples passed to the recognizer at once by default: a quarter of a second
+/// at 16 kHz.
+const DEFAULT_CHUNK_SIZE: usize = 4000;
+
+/// Something that happened while a [`Transcriber`] recognized a chunk of audio.
+#[derive(Debug)]
+pub enum Event {
+    /// The current utterance continues, and this is what was recognized of it so far
+    /// (see [`SpeechRecognizer::partial_result`]).
+    Partial(PartialResult<'static>),
+
+    /// The current utterance ended, with this result (see [`SpeechRecognizer::result`]).
+    Final(CompleteResult<'static>),
+
+    /// A chunk could not be recognized, or its result could not be parsed.
+    ///
+    /// The transcriber goes on with the next chunk, unless the error is about the final
+    /// result, in which case this is the last event instead of [`Event::EndOfStream`].
+    Failed(TranscriberError),
+
+    /// The audio ended, and this is the result of what remained of it
+    /// (see [`SpeechRecognizer::final_result`]). It is the last event.
+    EndOfStream(CompleteResult<'static>),
+}
+
+/// Possible errors reported by a [`Transcriber`] with [`Event::Failed`].
+#[derive(Error, Debug)]
+pub enum TranscriberError {
+    /// Error returned if the recognizer rejects a chunk.
+    #[error("the recognizer did not accept the audio")]
+    AcceptWaveform(#[from] AcceptWaveformError),
+
+    /// Error returned if the recognizer fails to decode the speech in a chunk
+    /// (see [`DecodingState::Failed`]).
+    #[error("the recognizer failed to recognize the audio")]
+    RecognitionFailed,
+
+    /// Error returned if a result cannot be parsed (see [`SpeechRecognizer::try_result`]).
+    #[error("could not read the result of the recognizer")]
+    Result(#[from] ResultError),
+}
+
+/// Feeds audio to a recognizer in chunks, and turns the state of the recognizer after each
+/// chunk into an [`Event`], with the matching result.
+///
+/// Results are retrieved with the `try_*` methods of [`SpeechRecognizer`], so results that
+/// cannot be parsed are reported with [`Event::Failed`] instead of panicking.
+///
+/// It is an iterator that yields one event per chunk, then [`Event::EndOfStream`] once the audio
+/// runs out. The audio is a source of 16-bit PCM samples, in the format the recognizer expects,
+/// that is only read as events are requested, so it can be a live stream.
+///
+/// # Examples
+///
+/// ```
+/// use vosk::{Event, ScriptedRecognizer, Transcriber};
+///
+/// let mut recognizer = ScriptedRecognizer::new();
+/// recognizer.push_partial_result_text("hello");
+/// recognizer.push_final_result_text("hello world");
+///
+/// let audio = vec![0i16; 6000];
+/// for event in Transcriber::new(recognizer, audio) {
+///     match event {
+///         Event::Partial(partial) => println!("...{}", partial.partial),
+///         Event::Final(result) | Event::EndOfStream(result) => {
+///             println!("{}", result.text().unwrap_or_default())
+///         }
+///         Event::Failed(error) => eprintln!("recognition failed: {error}"),
+///     }
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Transcriber<R, A> {
+    recognizer: R,
+    audio: A,
+    chunk_size: usize,
+    /// Buffer for the chunk being recognized.
+    chunk: Vec<i16>,
+    /// Whether [`Event::EndOfStream`] was returned.
+    ended: bool,
+}
+
+impl<R: SpeechRecognizer, A: Iterator<Item = i16>> Transcriber<R, A> {
+    /// Creates a transcriber that feeds `audio` to `recognizer`, in chunks of 4000 samples
+    /// (see [`with_chunk_size`](Self::with_chunk_size)).
+    ///
+    /// * `recognizer` - Any [`SpeechRecognizer`], such as a [`Recognizer`], which can also
+    ///   be borrowed mutably to use it after the transcription.
+    ///
+    /// [`Recognizer`]: crate::Recognizer
+    pub fn new(recognizer: R, audio: impl IntoIterator<IntoIter = A>) -> Self {
+        Self {
+            recognizer,
+            audio: audio.into_iter(),
+            chunk_size: DEFAULT_CHUNK_SIZE,
+            chunk: Vec::new(),
+            ended: false,
+        }
+    }
+
+    /// Sets the number of samples passed to the recognizer at once, and so how often
+    /// events are yielded. The last chunk can be shorter.
+    ///
+    /// Default: 4000, a quarter of a second at 16 kHz.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chunk_size` is 0.
+    #[must_use]
+    pub fn with_chunk_size(mut self, chunk_size: usize) -> Self {
+        assert!(chunk_size > 0, "the chunk size must not be 0");
+        self.chunk_size = chunk_size;
+        self
+    }
+
+    /// The recognizer audio is fed to.
+    pub fn recognizer(&self) -> &R {
+        &self.recognizer
+    }
+
+    /// Returns the recognizer, dropping the audio that was not read.
+    pub fn into_recognizer(self) -> R {
+        self.recognizer
+    }
+}
+
+impl<R: SpeechRecognizer, A: Iterator<Item = i16>> Iterator for Transcriber<R, A> {
+    type Item = Event;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.ended {
+            return None;
+        }
+
+        self.chunk.clear();
+        self.chunk.extend(self.audio.by_ref().take(self.chunk_size));
+
+        let event = if self.chunk.is_empty() {
+            self.ended = true;
+            self.recognizer.try_final_result().map(Event::EndOfStream)
+        } else {
+            match self.recognizer.accept_waveform(&self.chunk) {
+                Ok(DecodingState::Running) => {
+                    self.recognizer.try_partial_result().map(Event::Partial)
+                }
+                Ok(DecodingState::Finalized) => self.recognizer.try_result().map(Event::Final),
+                Ok(DecodingState::Failed) => {
+                    return Some(Event::Failed(TranscriberError::RecognitionFailed))
+                }
+                Err(error) => return Some(Event::Failed(error.into())),
+            }
+        };
+
+        Some(event.unwrap_or_else(|error| Event::Failed(error.into())))
+    }
+}
+
+impl<R: SpeechRecognizer, A: Iterator<Item = i16>> FusedIterator for Transcriber<R, A> {}
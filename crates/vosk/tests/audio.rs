@@ -0,0 +1,277 @@
+#![cfg(all(feature = "audio", feature = "mock"))]
+
+use std::{f32::consts::PI, path::PathBuf, time::Duration};
+
+use hound::{SampleFormat, WavSpec, WavWriter};
+use vosk::{
+    transcribe_file, transcribe_file_with, ChannelMix, Model, RecognizerError, ResultError,
+    ScriptedRecognizer, TranscribeError, TranscribeOptions,
+};
+use vosk_mock::{Call, DecodingState, FakeModelDir};
+
+/// Value of a sine wave at `frequency` Hz, with an amplitude of 0.5, at `time` seconds.
+fn sine(frequency: f32, time: f32) -> f32 {
+    0.5 * (2.0 * PI * frequency * time).sin()
+}
+
+/// Writes a WAV file in `model_dir` (so it is deleted with it) with `seconds` of a 440 Hz sine
+/// on the left channel and silence on the right one, at 44.1 kHz.
+fn write_wav(
+    model_dir: &FakeModelDir,
+    bits_per_sample: u16,
+    sample_format: SampleFormat,
+    seconds: f32,
+) -> PathBuf {
+    let path = model_dir.path().join("audio.wav");
+    let spec = WavSpec {
+        channels: 2,
+        sample_rate: 44100,
+        bits_per_sample,
+        sample_format,
+    };
+    let mut writer = WavWriter::create(&path, spec).unwrap();
+
+    for index in 0..(44100.0 * seconds) as usize {
+        let left = sine(440.0, index as f32 / 44100.0);
+        for sample in [left, 0.0] {
+            match (sample_format, bits_per_sample) {
+                (SampleFormat::Float, _) => writer.write_sample(sample).unwrap(),
+                (SampleFormat::Int, 8) => writer.write_sample((sample * 127.0) as i8).unwrap(),
+                (SampleFormat::Int, bits) => {
+                    let max = ((1i64 << (bits - 1)) - 1) as f32;
+                    writer.write_sample((sample * max) as i32).unwrap();
+                }
+            }
+        }
+    }
+
+    writer.finalize().unwrap();
+    path
+}
+
+/// Audio Vosk received, scaled back to floats between -1 and 1.
+fn received() -> Vec<f32> {
+    vosk_mock::calls()
+        .into_iter()
+        .flat_map(|call| match call {
+            Call::AcceptWaveformFloat(samples) => samples,
+            _ => Vec::new(),
+        })
+        .map(|sample| sample / 32768.0)
+        .collect()
+}
+
+/// Checks that `audio` is a 440 Hz sine at 16 kHz (the rate of the fake model),
+/// ignoring the edges, which the resampler smooths.
+fn assert_is_sine(audio: &[f32], seconds: f32, tolerance: f32) {
+    // The end of the audio is flushed from the resampler
+    let expected_len = (16000.0 * seconds) as usize;
+    assert!(
+        audio.len().abs_diff(expected_len) <= 1,
+        "{} samples",
+        audio.len()
+    );
+
+    for (index, sample) in audio.iter().enumerate().take(audio.len() - 100).skip(100) {
+        let expected = sine(440.0, index as f32 / 16000.0);
+        assert!(
+            (sample - expected).abs() < tolerance,
+            "sample {index}: {sample} instead of {expected}"
+        );
+    }
+}
+
+#[test]
+fn wav_is_decoded_in_every_sample_format() {
+    let formats = [
+        (8, SampleFormat::Int, 0.02),
+        (16, SampleFormat::Int, 0.005),
+        (24, SampleFormat::Int, 0.005),
+        (32, SampleFormat::Int, 0.005),
+        (32, SampleFormat::Float, 0.005),
+    ];
+
+    for (bits_per_sample, sample_format, tolerance) in formats {
+        let model_dir = FakeModelDir::new();
+        let model = Model::new(model_dir.path()).unwrap();
+        let path = write_wav(&model_dir, bits_per_sample, sample_format, 0.5);
+
+        vosk_mock::reset();
+        let options = TranscribeOptions {
+            channel_mix: ChannelMix::Channel(0),
+            ..TranscribeOptions::default()
+        };
+        let transcript = transcribe_file(&model, &path, options).unwrap();
+
+        assert_eq!(transcript.duration, Duration::from_millis(500));
+        assert_is_sine(&received(), 0.5, tolerance);
+    }
+}
+
+#[test]
+fn flac_is_decoded() {
+    let model_dir = FakeModelDir::new();
+    let model = Model::new(model_dir.path()).unwrap();
+
+    vosk_mock::reset();
+    let transcript = transcribe_file(
+        &model,
+        concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/audio/sine_440hz_22050hz.flac"
+        ),
+        TranscribeOptions::default(),
+    )
+    .unwrap();
+
+    // 5512 samples at 22.05 kHz
+    assert_eq!(
+        transcript.duration,
+        Duration::from_secs_f64(5512.0 / 22050.0)
+    );
+    assert_is_sine(&received(), 0.25, 0.005);
+}
+
+#[test]
+fn channels_are_mixed() {
+    let model_dir = FakeModelDir::new();
+    let model = Model::new(model_dir.path()).unwrap();
+    let path = write_wav(&model_dir, 32, SampleFormat::Float, 0.1);
+
+    vosk_mock::reset();
+    transcribe_file(&model, &path, TranscribeOptions::default()).unwrap();
+    let average = received();
+
+    vosk_mock::reset();
+    let options = TranscribeOptions {
+        channel_mix: ChannelMix::Channel(0),
+        ..TranscribeOptions::default()
+    };
+    transcribe_file(&model, &path, options).unwrap();
+    let left = received();
+
+    assert_eq!(average.len(), left.len());
+    for (average, left) in average.iter().zip(&left) {
+        assert!((average - left / 2.0).abs() < 1e-6);
+    }
+}
+
+#[test]
+fn results_are_collected() {
+    let model_dir = FakeModelDir::new();
+    let model = Model::new(model_dir.path()).unwrap();
+    let path = write_wav(&model_dir, 16, SampleFormat::Int, 0.5);
+
+    vosk_mock::reset();
+    vosk_mock::script_decoding_state(DecodingState::Finalized);
+    vosk_mock::script_decoding_state(DecodingState::Running);
+    vosk_mock::script_decoding_state(DecodingState::Finalized);
+    vosk_mock::script_result(r#"{"text": "hello"}"#);
+    vosk_mock::script_result(r#"{"text": ""}"#);
+    vosk_mock::script_final_result(r#"{"text": "world"}"#);
+
+    let options = TranscribeOptions {
+        words: true,
+        ..TranscribeOptions::default()
+    };
+    let transcript = transcribe_file(&model, &path, options).unwrap();
+
+    let texts: Vec<_> = transcript
+        .utterances
+        .iter()
+        .map(|result| result.text().unwrap())
+        .collect();
+    assert_eq!(texts, ["hello", ""]);
+    assert_eq!(transcript.final_result.text(), Some("world"));
+    assert_eq!(transcript.text(), "hello world");
+
+    let calls = vosk_mock::calls();
+    assert!(calls.contains(&Call::SetWords(true)));
+    assert!(calls.contains(&Call::FinalResult));
+}
+
+#[test]
+fn errors_are_reported() {
+    let model_dir = FakeModelDir::new();
+    let model = Model::new(model_dir.path()).unwrap();
+
+    assert!(matches!(
+        transcribe_file(
+            &model,
+            model_dir.path().join("missing.wav"),
+            TranscribeOptions::default()
+        ),
+        Err(TranscribeError::Io(_))
+    ));
+
+    let not_audio = model_dir.path().join("not_audio.wav");
+    std::fs::write(&not_audio, "this is not audio").unwrap();
+    assert!(matches!(
+        transcribe_file(&model, &not_audio, TranscribeOptions::default()),
+        Err(TranscribeError::Decode(_))
+    ));
+
+    let path = write_wav(&model_dir, 16, SampleFormat::Int, 0.1);
+    let options = TranscribeOptions {
+        channel_mix: ChannelMix::Channel(2),
+        ..TranscribeOptions::default()
+    };
+    assert!(matches!(
+        transcribe_file(&model, &path, options),
+        Err(TranscribeError::Recognizer(
+            RecognizerError::InvalidChannels { channels: 2, .. }
+        ))
+    ));
+
+    vosk_mock::reset();
+    vosk_mock::script_decoding_state(DecodingState::Failed);
+    assert!(matches!(
+        transcribe_file(&model, &path, TranscribeOptions::default()),
+        Err(TranscribeError::RecognitionFailed)
+    ));
+
+    vosk_mock::reset();
+    vosk_mock::script_decoding_state(DecodingState::Finalized);
+    vosk_mock::script_result("not json");
+    assert!(matches!(
+        transcribe_file(&model, &path, TranscribeOptions::default()),
+        Err(TranscribeError::Result(ResultError::InvalidJson { .. }))
+    ));
+}
+
+#[test]
+fn any_recognizer_can_transcribe_files() {
+    let flac = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/audio/sine_440hz_22050hz.flac"
+    );
+
+    let mut recognizer = ScriptedRecognizer::new();
+    recognizer.push_decoding_state(vosk::DecodingState::Finalized);
+    recognizer.push_result_text("hello");
+    recognizer.push_final_result_text("world");
+
+    let transcript =
+        transcribe_file_with(&mut recognizer, 16000.0, flac, ChannelMix::Average).unwrap();
+
+    assert_eq!(transcript.text(), "hello world");
+    assert_eq!(
+        transcript.duration,
+        Duration::from_secs_f64(5512.0 / 22050.0)
+    );
+
+    let received: Vec<f32> = recognizer
+        .accepted_samples()
+        .iter()
+        .map(|&sample| f32::from(sample) / 32768.0)
+        .collect();
+    // Including the end of the audio, flushed from the resampler
+    assert_is_sine(&received, 0.25, 0.005);
+
+    assert!(matches!(
+        transcribe_file_with(ScriptedRecognizer::new(), 0.0, flac, ChannelMix::Average),
+        Err(TranscribeError::Recognizer(
+            RecognizerError::InvalidSampleRate(_)
+        ))
+    ));
+}
@@ -0,0 +1,154 @@
+#![cfg(feature = "mock")]
+
+use vosk::{AcceptWaveformError, ChannelMix, Model, Recognizer, RecognizerError};
+use vosk_mock::{Call, FakeModelDir};
+
+/// Accepts `data` with `channels` mixed by `mix`, and returns the call Vosk received.
+fn accepted(channels: u16, mix: ChannelMix, data: &[i16]) -> Call {
+    let model_dir = FakeModelDir::new();
+    let model = Model::new(model_dir.path()).unwrap();
+    let mut recognizer = Recognizer::new(&model, 16000.0).unwrap();
+    recognizer.set_input_channels(channels, mix).unwrap();
+
+    vosk_mock::reset();
+    recognizer.accept_waveform(data).unwrap();
+    vosk_mock::take_calls().remove(0)
+}
+
+const STEREO: [i16; 6] = [100, 300, -50, 10, 0, -2000];
+
+#[test]
+fn mono_is_passed_unchanged() {
+    assert_eq!(
+        accepted(1, ChannelMix::Average, &[1, 2, 3]),
+        Call::AcceptWaveformShort(vec![1, 2, 3])
+    );
+}
+
+#[test]
+fn channels_are_averaged() {
+    assert_eq!(
+        accepted(2, ChannelMix::Average, &STEREO),
+        Call::AcceptWaveformFloat(vec![200.0, -20.0, -1000.0])
+    );
+    assert_eq!(
+        accepted(
+            3,
+            ChannelMix::Average,
+            &[3, 6, 9, i16::MAX, i16::MAX, i16::MAX]
+        ),
+        Call::AcceptWaveformFloat(vec![6.0, 32767.0])
+    );
+}
+
+#[test]
+fn a_single_channel_can_be_picked() {
+    assert_eq!(
+        accepted(2, ChannelMix::Channel(0), &STEREO),
+        Call::AcceptWaveformFloat(vec![100.0, -50.0, 0.0])
+    );
+    assert_eq!(
+        accepted(2, ChannelMix::Channel(1), &STEREO),
+        Call::AcceptWaveformFloat(vec![300.0, 10.0, -2000.0])
+    );
+}
+
+#[test]
+fn the_loudest_channel_of_each_buffer_is_kept() {
+    assert_eq!(
+        accepted(2, ChannelMix::Loudest, &STEREO),
+        Call::AcceptWaveformFloat(vec![300.0, 10.0, -2000.0])
+    );
+    // Louder over the whole buffer, although the other channel has the loudest sample
+    assert_eq!(
+        accepted(2, ChannelMix::Loudest, &[900, -1000, -900, 0, 900, 0]),
+        Call::AcceptWaveformFloat(vec![900.0, -900.0, 900.0])
+    );
+    // The first channel wins ties
+    assert_eq!(
+        accepted(3, ChannelMix::Loudest, &[0, 5, -5, 0, -5, 5]),
+        Call::AcceptWaveformFloat(vec![5.0, -5.0])
+    );
+}
+
+#[test]
+fn other_sample_formats_are_mixed() {
+    let model_dir = FakeModelDir::new();
+    let model = Model::new(model_dir.path()).unwrap();
+    let mut recognizer = Recognizer::new(&model, 16000.0).unwrap();
+    recognizer
+        .set_input_channels(2, ChannelMix::Average)
+        .unwrap();
+
+    vosk_mock::reset();
+    recognizer.accept_samples(&[0.5f32, 0.25]).unwrap();
+    recognizer
+        .accept_samples(&[0x64u8, 0x00, 0x2c, 0x01])
+        .unwrap();
+
+    assert_eq!(
+        vosk_mock::calls(),
+        [
+            Call::AcceptWaveformFloat(vec![12288.0]),
+            Call::AcceptWaveformFloat(vec![200.0]),
+        ]
+    );
+}
+
+#[test]
+fn partial_frames_are_rejected() {
+    let model_dir = FakeModelDir::new();
+    let model = Model::new(model_dir.path()).unwrap();
+    let mut recognizer = Recognizer::new(&model, 16000.0).unwrap();
+    recognizer
+        .set_input_channels(2, ChannelMix::Loudest)
+        .unwrap();
+
+    vosk_mock::reset();
+    assert!(matches!(
+        recognizer.accept_waveform(&[0i16; 3]),
+        Err(AcceptWaveformError::PartialFrame {
+            samples: 3,
+            channels: 2
+        })
+    ));
+    // Three samples, as bytes
+    assert!(matches!(
+        recognizer.accept_samples(&[0u8; 6]),
+        Err(AcceptWaveformError::PartialFrame {
+            samples: 3,
+            channels: 2
+        })
+    ));
+    assert_eq!(vosk_mock::calls(), []);
+}
+
+#[test]
+fn invalid_layouts_are_rejected() {
+    let model_dir = FakeModelDir::new();
+    let model = Model::new(model_dir.path()).unwrap();
+    let mut recognizer = Recognizer::new(&model, 16000.0).unwrap();
+
+    assert!(matches!(
+        recognizer.set_input_channels(0, ChannelMix::Average),
+        Err(RecognizerError::InvalidChannels {
+            channels: 0,
+            mix: ChannelMix::Average
+        })
+    ));
+    assert!(matches!(
+        recognizer.set_input_channels(2, ChannelMix::Channel(2)),
+        Err(RecognizerError::InvalidChannels {
+            channels: 2,
+            mix: ChannelMix::Channel(2)
+        })
+    ));
+
+    // The previous layout is kept
+    vosk_mock::reset();
+    recognizer.accept_waveform(&[1i16, 2, 3]).unwrap();
+    assert_eq!(
+        vosk_mock::calls(),
+        [Call::AcceptWaveformShort(vec![1, 2, 3])]
+    );
+}
@@ -0,0 +1,269 @@
+//! Checks the results in `tests/corpus`, shaped like the output of Vosk, are parsed as expected:
+//! * `complete/single_*.json` as a `CompleteResult::Single`.
+//! * `complete/multiple_*.json` as a `CompleteResult::Multiple`.
+//! * `partial/*.json` as a `PartialResult`.
+//! * `nlsml/*.xml` as a `NlsmlResult`.
+//! * `invalid/*.txt` neither as a complete result, a partial one nor an NLSML one.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+use vosk::{CompleteResult, NlsmlResult, PartialResult};
+
+fn corpus(dir: &str) -> Vec<(String, String)> {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/corpus")
+        .join(dir);
+
+    let mut files: Vec<PathBuf> = fs::read_dir(&dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .collect();
+    files.sort();
+    assert!(!files.is_empty(), "no files in {}", dir.display());
+
+    files
+        .into_iter()
+        .map(|path| {
+            let name = path.file_stem().unwrap().to_str().unwrap().to_owned();
+            (name, fs::read_to_string(path).unwrap())
+        })
+        .collect()
+}
+
+fn check_complete(name: &str, result: &CompleteResult) {
+    if name.starts_with("single_") {
+        assert!(
+            matches!(result, CompleteResult::Single(_)),
+            "{name} is not a single result: {result:?}"
+        );
+    } else if name.starts_with("multiple_") {
+        assert!(
+            matches!(result, CompleteResult::Multiple(_)),
+            "{name} is not a multiple result: {result:?}"
+        );
+    } else {
+        panic!("{name} should start with single_ or multiple_");
+    }
+}
+
+#[test]
+fn complete_results_are_parsed() {
+    for (name, json) in corpus("complete") {
+        let result: CompleteResult = serde_json::from_str(&json)
+            .unwrap_or_else(|error| panic!("{name} could not be parsed: {error}"));
+        check_complete(&name, &result);
+
+        // Serializing keeps every field that was understood
+        let serialized = serde_json::to_string(&result).unwrap();
+        let reparsed: CompleteResult = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(reparsed, result, "{name} changed when serialized");
+    }
+}
+
+#[test]
+fn partial_results_are_parsed() {
+    for (name, json) in corpus("partial") {
+        let result: PartialResult = serde_json::from_str(&json)
+            .unwrap_or_else(|error| panic!("{name} could not be parsed: {error}"));
+
+        let serialized = serde_json::to_string(&result).unwrap();
+        let reparsed: PartialResult = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(reparsed, result, "{name} changed when serialized");
+    }
+}
+
+#[test]
+fn invalid_results_are_rejected() {
+    for (name, text) in corpus("invalid") {
+        assert!(
+            serde_json::from_str::<CompleteResult>(&text).is_err(),
+            "{name} was parsed as a complete result"
+        );
+        assert!(
+            serde_json::from_str::<PartialResult>(&text).is_err(),
+            "{name} was parsed as a partial result"
+        );
+        assert!(
+            NlsmlResult::from_xml(&text).is_err(),
+            "{name} was parsed as an NLSML result"
+        );
+    }
+}
+
+#[test]
+fn nlsml_results_are_parsed() {
+    for (name, xml) in corpus("nlsml") {
+        let result = NlsmlResult::from_xml(&xml)
+            .unwrap_or_else(|error| panic!("{name} could not be parsed: {error}"));
+
+        assert!(
+            !result.interpretations.is_empty(),
+            "{name} has no interpretations"
+        );
+        assert_eq!(result.raw_xml, xml);
+    }
+
+    let (_, xml) = corpus("nlsml")
+        .into_iter()
+        .find(|(name, _)| name == "n_best")
+        .unwrap();
+    let result = NlsmlResult::from_xml(&xml).unwrap();
+    assert_eq!(result.grammar.as_deref(), Some("default"));
+    assert_eq!(result.text(), Some("one zero zero zero one"));
+    assert_eq!(result.interpretations.len(), 2);
+
+    let second = &result.interpretations[1];
+    assert_eq!(second.grammar.as_deref(), Some("default"));
+    assert_eq!(second.confidence, 226.473_82);
+    assert_eq!(second.input.as_deref(), Some("one zero zero zero one nah"));
+    assert_eq!(second.input_mode.as_deref(), Some("speech"));
+    assert_eq!(second.instance, "one zero zero zero one nah");
+}
+
+#[test]
+fn nlsml_without_input_has_no_text() {
+    let (_, xml) = corpus("nlsml")
+        .into_iter()
+        .find(|(name, _)| name == "noinput")
+        .unwrap();
+    let result = NlsmlResult::from_xml(&xml).unwrap();
+
+    assert_eq!(result.text(), None);
+    assert_eq!(result.best().unwrap().confidence, 1.0);
+    assert_eq!(result.best().unwrap().instance, "");
+}
+
+#[test]
+fn contents_are_kept() {
+    let corpus = corpus("complete");
+    let find = |name: &str| -> CompleteResult {
+        let (_, json) = corpus.iter().find(|(other, _)| other == name).unwrap();
+        serde_json::from_str(json).unwrap()
+    };
+
+    let words = find("single_words").single().unwrap();
+    assert_eq!(words.text, "what zero");
+    assert_eq!(words.result.len(), 2);
+    assert_eq!(words.result[1].word, "zero");
+    assert_eq!(words.result[1].conf, 0.934368);
+    assert_eq!(words.result[1].start, 1.11);
+    assert_eq!(words.result[1].end, 1.53);
+
+    let speaker = find("single_speaker").single().unwrap();
+    let speaker_info = speaker.speaker_info.unwrap();
+    assert_eq!(speaker_info.frames, 415);
+    assert_eq!(speaker_info.vector.len(), 5);
+    assert!(find("single_text").single().unwrap().speaker_info.is_none());
+
+    assert_eq!(
+        find("single_unicode").single().unwrap().text,
+        r#"привет мир 你好 "quoted" back\slash é"#
+    );
+
+    let speaker = find("multiple_speaker").multiple().unwrap();
+    let speaker_info = speaker.speaker_info.unwrap();
+    assert_eq!(speaker_info.frames, 415);
+    assert_eq!(speaker_info.vector[0], -0.645_632);
+    assert_eq!(speaker.alternatives.len(), 2);
+    assert_eq!(speaker.alternatives[1].text, "one zero zero zero one nah");
+
+    let speaker_words = find("multiple_speaker_words").multiple().unwrap();
+    assert_eq!(
+        speaker_words.speaker_info.unwrap().vector,
+        [0.5, -1.25, 2.0]
+    );
+    assert_eq!(speaker_words.alternatives[0].result[0].word, "one");
+
+    assert!(find("multiple_text")
+        .multiple()
+        .unwrap()
+        .speaker_info
+        .is_none());
+
+    let alternatives = find("multiple_words").multiple().unwrap().alternatives;
+    assert_eq!(alternatives.len(), 2);
+    assert_eq!(alternatives[0].confidence, 229.788_44);
+    assert_eq!(alternatives[0].result[1].word, "zero");
+    assert_eq!(alternatives[1].text, "onezero");
+}
+
+#[cfg(feature = "mock")]
+mod recognizer {
+    use super::*;
+    use vosk::{Model, Recognizer, ResultError};
+    use vosk_mock::FakeModelDir;
+
+    #[test]
+    fn try_result_accepts_the_corpus() {
+        let model_dir = FakeModelDir::new();
+        let model = Model::new(model_dir.path()).unwrap();
+        let mut recognizer = Recognizer::new(&model, 16000.0).unwrap();
+
+        for (name, json) in corpus("complete") {
+            vosk_mock::script_result(json.clone());
+            vosk_mock::script_final_result(json);
+
+            check_complete(&name, &recognizer.try_result().unwrap());
+            check_complete(&name, &recognizer.try_final_result().unwrap());
+        }
+
+        for (_, json) in corpus("partial") {
+            vosk_mock::script_partial_result(json);
+            recognizer.try_partial_result().unwrap();
+        }
+
+        for (_, xml) in corpus("nlsml") {
+            vosk_mock::script_result(xml.clone());
+            let result = recognizer.try_result().unwrap().nlsml().unwrap();
+            assert_eq!(result.raw_xml, xml);
+        }
+    }
+
+    #[test]
+    fn try_result_returns_the_raw_text_of_invalid_results() {
+        let model_dir = FakeModelDir::new();
+        let model = Model::new(model_dir.path()).unwrap();
+        let mut recognizer = Recognizer::new(&model, 16000.0).unwrap();
+
+        for (name, text) in corpus("invalid") {
+            vosk_mock::script_result(text.clone());
+            vosk_mock::script_partial_result(text.clone());
+            vosk_mock::script_final_result(text.clone());
+
+            let complete_errors = [
+                recognizer.try_result().unwrap_err(),
+                recognizer.try_final_result().unwrap_err(),
+            ];
+            let partial_error = recognizer.try_partial_result().unwrap_err();
+
+            for error in complete_errors {
+                if name.starts_with("nlsml_") {
+                    assert!(matches!(error, ResultError::InvalidNlsml { .. }));
+                } else {
+                    assert!(matches!(error, ResultError::InvalidJson { .. }));
+                }
+                assert_eq!(error.raw_text(), text, "unexpected error for {name}");
+            }
+
+            assert!(matches!(partial_error, ResultError::InvalidJson { .. }));
+            assert_eq!(
+                partial_error.raw_text(),
+                text,
+                "unexpected error for {name}"
+            );
+        }
+    }
+
+    #[test]
+    #[should_panic = "vosk returned a result that could not be parsed: \"not json\""]
+    fn result_panics_on_invalid_results() {
+        let model_dir = FakeModelDir::new();
+        let model = Model::new(model_dir.path()).unwrap();
+        let mut recognizer = Recognizer::new(&model, 16000.0).unwrap();
+
+        vosk_mock::script_result("not json");
+        let _ = recognizer.result();
+    }
+}
@@ -0,0 +1,36 @@
+#![cfg(feature = "dynamic")]
+
+use vosk::{is_library_loaded, load_library, LoadError};
+
+#[test]
+fn missing_library_cannot_be_opened() {
+    let error = load_library("/nonexistent/libvosk.so").unwrap_err();
+
+    assert!(matches!(error, LoadError::Open { path, .. } if path.ends_with("libvosk.so")));
+    assert!(!is_library_loaded());
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn missing_symbols_are_listed() {
+    // Any library that is not libvosk will do
+    let error = load_library("libc.so.6").unwrap_err();
+
+    let LoadError::MissingSymbols { symbols, .. } = error else {
+        panic!("unexpected error: {error}");
+    };
+    assert!(symbols.contains(&"vosk_model_new"));
+    assert!(symbols.contains(&"vosk_recognizer_result"));
+
+    // Newer functions are optional, so that older versions of Vosk can be loaded
+    for optional in [
+        "vosk_recognizer_set_grm",
+        "vosk_recognizer_set_endpointer_mode",
+        "vosk_text_processor_new",
+        "vosk_batch_model_new",
+        "vosk_gpu_init",
+    ] {
+        assert!(!symbols.contains(&optional), "{optional}");
+    }
+    assert!(!is_library_loaded());
+}
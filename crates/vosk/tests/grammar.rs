@@ -0,0 +1,180 @@
+use proptest::prelude::*;
+use vosk::{grammar_to_json, Grammar, GrammarError, GrammarManager};
+
+#[cfg(feature = "mock")]
+use vosk::{Model, OutOfVocabulary, Recognizer, RecognizerError};
+#[cfg(feature = "mock")]
+use vosk_mock::{Call, FakeModelDir};
+
+/// Encodes `phrases` the way Vosk expects them: a JSON array in which
+/// only quotes and backslashes need escaping, as control characters are rejected.
+#[cfg(feature = "mock")]
+fn expected_json(phrases: &[String]) -> String {
+    let phrases: Vec<_> = phrases
+        .iter()
+        .map(|phrase| format!("\"{}\"", phrase.replace('\\', "\\\\").replace('"', "\\\"")))
+        .collect();
+
+    format!("[{}]", phrases.join(","))
+}
+
+/// Returns the grammars Vosk received when creating a recognizer with `phrases`
+/// and when setting them again on it.
+#[cfg(feature = "mock")]
+fn grammars_received(phrases: &[String]) -> Result<Vec<String>, RecognizerError> {
+    let model_dir = FakeModelDir::new();
+    let model = Model::new(model_dir.path()).unwrap();
+
+    vosk_mock::reset();
+    let mut recognizer = Recognizer::new_with_grammar(&model, 16000.0, phrases)?;
+    recognizer.set_grammar(&Grammar::from(phrases))?;
+
+    Ok(vosk_mock::take_calls()
+        .into_iter()
+        .filter_map(|call| match call {
+            Call::RecognizerNewGrammar { grammar, .. } | Call::SetGrammar { grammar } => {
+                Some(grammar)
+            }
+            _ => None,
+        })
+        .collect())
+}
+
+#[cfg(feature = "mock")]
+#[test]
+fn special_characters_reach_vosk_escaped() {
+    for (phrases, expected) in [
+        (vec![r#"say "hi""#], r#"["say \"hi\""]"#),
+        (vec![r"back\slash", r#"\""#], r#"["back\\slash","\\\""]"#),
+        (
+            vec!["café", "日本語", "ñandú"],
+            r#"["café","日本語","ñandú"]"#,
+        ),
+        (vec!["[unk]", "a/b", "{x}"], r#"["[unk]","a/b","{x}"]"#),
+        (vec![""], r#"[""]"#),
+    ] {
+        let phrases: Vec<String> = phrases.into_iter().map(String::from).collect();
+
+        assert_eq!(grammars_received(&phrases).unwrap(), [expected; 2]);
+    }
+}
+
+proptest! {
+    #[cfg(feature = "mock")]
+    #[test]
+    fn phrases_reach_vosk_escaped(
+        phrases in prop::collection::vec("[\"\\\\/\\[\\]{}' a-zéñ日本]*", 0..8),
+    ) {
+        let expected = expected_json(&phrases);
+
+        prop_assert_eq!(grammars_received(&phrases).unwrap(), [expected.clone(), expected]);
+    }
+
+    #[cfg(feature = "mock")]
+    #[test]
+    fn phrases_with_control_characters_never_reach_vosk(
+        before in "[^\\p{Cc}]*",
+        character in "\\p{Cc}",
+        after in ".*",
+    ) {
+        let phrases = vec!["valid phrase".to_owned(), format!("{before}{character}{after}")];
+
+        let rejected = matches!(
+            grammars_received(&phrases),
+            Err(RecognizerError::Grammar(GrammarError::ControlCharacter { index: 1, .. }))
+        );
+        let passed_to_vosk = vosk_mock::calls().iter().any(|call| {
+            matches!(call, Call::RecognizerNewGrammar { .. } | Call::SetGrammar { .. })
+        });
+
+        prop_assert!(rejected);
+        prop_assert!(!passed_to_vosk);
+    }
+
+    #[test]
+    fn control_characters_are_rejected(
+        before in "[^\\p{Cc}]*",
+        character in "\\p{Cc}",
+        after in ".*",
+    ) {
+        let phrase = format!("{before}{character}{after}");
+        let character = character.chars().next().unwrap();
+
+        prop_assert_eq!(
+            grammar_to_json(&["valid phrase", &phrase]),
+            Err(GrammarError::ControlCharacter { index: 1, phrase: phrase.clone(), character })
+        );
+    }
+}
+
+#[cfg(feature = "mock")]
+#[test]
+fn grammars_are_validated_against_the_vocabulary() {
+    let model_dir = FakeModelDir::new();
+    let model = Model::new(model_dir.path()).unwrap();
+    vosk_mock::set_vocabulary(["turn", "on", "off", "the", "lights"]);
+
+    let grammar = Grammar::new()
+        .phrase("turn on the lights")
+        .phrase("turn  off")
+        .with_unknown();
+    assert_eq!(grammar.validate(&model), Ok(()));
+
+    let grammar = Grammar::new()
+        .phrase("turn on the lights")
+        .phrase("dim the lamp")
+        .phrase("[unk]")
+        .phrase("switch off");
+    assert_eq!(
+        grammar.validate(&model),
+        Err(GrammarError::OutOfVocabulary(vec![
+            OutOfVocabulary {
+                index: 1,
+                phrase: "dim the lamp".to_owned(),
+                words: vec!["dim".to_owned(), "lamp".to_owned()],
+            },
+            OutOfVocabulary {
+                index: 3,
+                phrase: "switch off".to_owned(),
+                words: vec!["switch".to_owned()],
+            },
+        ]))
+    );
+
+    // `[unk]` is only skipped as a whole phrase
+    let grammar = Grammar::new().phrase("turn [unk]");
+    assert!(matches!(
+        grammar.validate(&model),
+        Err(GrammarError::OutOfVocabulary(phrases)) if phrases[0].words == ["[unk]"]
+    ));
+}
+
+#[test]
+fn empty_grammar_is_an_empty_array() {
+    assert_eq!(grammar_to_json(&[] as &[&str]).unwrap(), "[]");
+}
+
+#[test]
+fn grammar_manager_keeps_a_set_of_phrases() {
+    let mut manager = GrammarManager::new().with_unknown();
+
+    assert!(manager.add("go back"));
+    assert!(manager.add("open settings"));
+    assert!(!manager.add("go back"));
+    assert!(manager.remove("open settings"));
+    assert!(!manager.remove("open settings"));
+    assert!(manager.add("help"));
+
+    assert_eq!(manager.phrases(), ["go back", "help"]);
+    assert_eq!(
+        manager.grammar(),
+        Grammar::new()
+            .phrase("go back")
+            .phrase("help")
+            .phrase("[unk]")
+    );
+
+    manager.replace(["yes", "no", "yes"]);
+    assert_eq!(manager.phrases(), ["yes", "no"]);
+    assert!(manager.has_changes());
+}
@@ -0,0 +1,194 @@
+use vosk::{CompileError, CompiledGrammar, GrammarCompiler};
+
+fn texts(grammar: &CompiledGrammar) -> Vec<&str> {
+    grammar
+        .phrases()
+        .iter()
+        .map(|phrase| phrase.text.as_str())
+        .collect()
+}
+
+#[test]
+fn jsgf_alternatives_optionals_and_references_are_expanded() {
+    let grammar = CompiledGrammar::from_jsgf(
+        "#JSGF V1.0 UTF-8 en;
+        grammar lights;
+
+        // Comments are ignored
+        public <command> = <action> [the] lights;
+        <action> = /10/ turn on | /1/ turn off; /* and so are block comments */",
+    )
+    .unwrap();
+
+    assert_eq!(
+        texts(&grammar),
+        [
+            "turn on lights",
+            "turn on the lights",
+            "turn off lights",
+            "turn off the lights",
+        ]
+    );
+}
+
+#[test]
+fn jsgf_repetitions_are_bounded() {
+    let grammar = GrammarCompiler::new()
+        .max_repeat(2)
+        .compile_jsgf("public <digits> = (one | two)+;")
+        .unwrap();
+
+    assert_eq!(grammar.phrases().len(), 2 + 4);
+}
+
+#[test]
+fn recognized_text_maps_back_to_rule_and_tags() {
+    let grammar = CompiledGrammar::from_jsgf(
+        "public <lights> = turn (on {on} | off {off}) [the] lights {lights};
+        public <stop> = stop {stop};",
+    )
+    .unwrap();
+
+    let phrase = grammar.interpret(" turn off  the lights").unwrap();
+    assert_eq!(phrase.rule, "lights");
+    assert_eq!(phrase.tags, ["off", "lights"]);
+
+    let phrase = grammar.interpret("stop").unwrap();
+    assert_eq!(phrase.rule, "stop");
+    assert_eq!(phrase.tags, ["stop"]);
+
+    assert!(grammar.interpret("turn lights").is_none());
+}
+
+#[test]
+fn jsgf_errors_have_line_and_column() {
+    assert_eq!(
+        CompiledGrammar::from_jsgf("public <a> = one\n  | two ;;"),
+        Err(CompileError::Syntax {
+            line: 2,
+            column: 10,
+            message: "unexpected ';'".to_owned(),
+        })
+    );
+
+    assert_eq!(
+        CompiledGrammar::from_jsgf("public <a> = one;\n<b> = (two | three;"),
+        Err(CompileError::Syntax {
+            line: 2,
+            column: 19,
+            message: "expected ')', found ';'".to_owned(),
+        })
+    );
+
+    assert_eq!(
+        CompiledGrammar::from_jsgf("public <a> = one <c>;"),
+        Err(CompileError::UndefinedRule {
+            rule: "c".to_owned(),
+            line: 1,
+            column: 18,
+        })
+    );
+}
+
+#[test]
+fn recursive_rules_are_rejected() {
+    assert_eq!(
+        CompiledGrammar::from_jsgf("public <a> = one [<b>]; <b> = two <a>;"),
+        Err(CompileError::RecursiveRule("a".to_owned()))
+    );
+}
+
+#[test]
+fn expansion_size_is_limited() {
+    let source = "public <a> = <d> <d> <d> <d>; <d> = one | two | three | four | five;";
+
+    assert_eq!(
+        GrammarCompiler::new().max_phrases(100).compile_jsgf(source),
+        Err(CompileError::TooManyPhrases(100))
+    );
+    assert_eq!(
+        GrammarCompiler::new()
+            .max_phrases(625)
+            .compile_jsgf(source)
+            .unwrap()
+            .phrases()
+            .len(),
+        625
+    );
+}
+
+#[test]
+fn grammars_without_public_rules_are_rejected() {
+    assert_eq!(
+        CompiledGrammar::from_jsgf("<a> = one;"),
+        Err(CompileError::NoRootRule)
+    );
+}
+
+#[test]
+fn srgs_is_expanded() {
+    let grammar = CompiledGrammar::from_srgs(
+        r##"<?xml version="1.0"?>
+        <grammar xmlns="http://www.w3.org/2001/06/grammar" version="1.0" root="command">
+          <rule id="command" scope="public">
+            turn
+            <one-of>
+              <item>on<tag>on</tag></item>
+              <item>off<tag>off</tag></item>
+            </one-of>
+            <item repeat="0-1">the</item>
+            <ruleref uri="#object"/>
+          </rule>
+          <rule id="object">
+            <one-of>
+              <item>lights</item>
+              <item><token>tv</token></item>
+            </one-of>
+          </rule>
+        </grammar>"##,
+    )
+    .unwrap();
+
+    assert_eq!(grammar.phrases().len(), 8);
+
+    let phrase = grammar.interpret("turn off the tv").unwrap();
+    assert_eq!(phrase.rule, "command");
+    assert_eq!(phrase.tags, ["off"]);
+}
+
+#[test]
+fn srgs_errors_have_line_and_column() {
+    assert_eq!(
+        CompiledGrammar::from_srgs(
+            "<grammar root=\"a\">\n  <rule id=\"a\">\n    <item repeat=\"x\">one</item>\n  </rule>\n</grammar>"
+        ),
+        Err(CompileError::Syntax {
+            line: 3,
+            column: 5,
+            message: "invalid repeat \"x\"".to_owned(),
+        })
+    );
+
+    assert!(matches!(
+        CompiledGrammar::from_srgs("<grammar root=\"a\">\n  <rule id=\"a\">one</item>"),
+        Err(CompileError::Syntax { line: 2, .. })
+    ));
+}
+
+#[test]
+fn srgs_repetitions_of_nothing_are_expanded_once() {
+    let grammar = CompiledGrammar::from_srgs(
+        r#"<grammar root="a">
+          <rule id="a">
+            hello
+            <item repeat="0-4294967295"><tag>greeting</tag></item>
+            <item repeat="4294967295"><ruleref special="NULL"/></item>
+            world
+          </rule>
+        </grammar>"#,
+    )
+    .unwrap();
+
+    assert_eq!(texts(&grammar), ["hello world"]);
+    assert_eq!(grammar.phrases()[0].tags, Vec::<String>::new());
+}
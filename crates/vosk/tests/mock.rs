@@ -0,0 +1,379 @@
+#![cfg(feature = "mock")]
+
+use std::time::Duration;
+use vosk::{
+    DecodingState, EndpointerDelays, EndpointerMode, Grammar, GrammarManager, GraphKind, Model,
+    ModelError, Recognizer, RecognizerError, SharedModel, SharedSpeakerModel, SpeechRecognizer,
+    TextProcessor,
+};
+use vosk_mock::{Call, FakeModelDir};
+
+#[test]
+fn settings_are_passed_to_vosk() {
+    let model_dir = FakeModelDir::new();
+    let model = Model::new(model_dir.path()).unwrap();
+    let mut recognizer = Recognizer::new(&model, 8000.0).unwrap();
+
+    recognizer.set_max_alternatives(3);
+    recognizer.set_words(true);
+    recognizer.set_partial_words(false);
+    recognizer.set_endpointer_mode(EndpointerMode::Long);
+    recognizer.set_endpointer_delays(EndpointerDelays {
+        initial_silence: Duration::from_secs(5),
+        trailing_silence: Duration::from_millis(500),
+        max_utterance: Duration::from_secs(20),
+    });
+    drop(recognizer);
+
+    assert_eq!(
+        vosk_mock::take_calls()[1..],
+        [
+            Call::RecognizerNew {
+                sample_rate: 8000.0
+            },
+            Call::SetMaxAlternatives(3),
+            Call::SetWords(true),
+            Call::SetPartialWords(false),
+            Call::SetEndpointerMode(vosk_sys::VoskEndpointerMode_VOSK_EP_ANSWER_LONG),
+            Call::SetEndpointerDelays {
+                t_start_max: 5.0,
+                t_end: 0.5,
+                t_max: 20.0
+            },
+            Call::RecognizerFree,
+        ]
+    );
+    assert_eq!(vosk_mock::misuses(), Vec::<String>::new());
+}
+
+#[test]
+fn audio_and_results_are_passed_through() {
+    let model_dir = FakeModelDir::new();
+    let model = Model::new(model_dir.path()).unwrap();
+    let mut recognizer = Recognizer::new(&model, 16000.0).unwrap();
+
+    vosk_mock::script_decoding_state(vosk_mock::DecodingState::Finalized);
+    vosk_mock::script_result(
+        r#"{"result": [{"conf": 1.0, "start": 0.5, "end": 0.9, "word": "hello"}], "text": "hello"}"#,
+    );
+    vosk_mock::script_partial_result(r#"{"partial": "hel"}"#);
+
+    assert_eq!(
+        recognizer.accept_waveform(&[1i16, -2, 3]).unwrap(),
+        DecodingState::Finalized
+    );
+    assert_eq!(
+        recognizer.accept_waveform(&[]).unwrap(),
+        DecodingState::Running
+    );
+    assert_eq!(recognizer.partial_result().partial, "hel");
+
+    let result = recognizer.result().single().unwrap();
+    assert_eq!(result.text, "hello");
+    assert_eq!(result.result[0].word, "hello");
+    assert_eq!(result.result[0].start, 0.5);
+
+    assert_eq!(recognizer.final_result().single().unwrap().text, "");
+
+    let calls = vosk_mock::calls();
+    assert!(calls.contains(&Call::AcceptWaveformShort(vec![1, -2, 3])));
+    assert!(calls.contains(&Call::AcceptWaveformShort(vec![])));
+}
+
+#[test]
+fn grammars_are_passed_as_json() {
+    let model_dir = FakeModelDir::new();
+    let model = Model::new(model_dir.path()).unwrap();
+    let mut recognizer =
+        Recognizer::new_with_grammar(&model, 16000.0, &["yes", "no", "[unk]"]).unwrap();
+
+    let mut manager = GrammarManager::from_iter(["start", "stop"]);
+    assert!(manager.apply(&mut recognizer).unwrap());
+    assert!(!manager.apply(&mut recognizer).unwrap());
+
+    recognizer.set_grammar(&Grammar::new()).unwrap();
+
+    let grammars: Vec<_> = vosk_mock::calls()
+        .into_iter()
+        .filter_map(|call| match call {
+            Call::RecognizerNewGrammar { grammar, .. } | Call::SetGrammar { grammar } => {
+                Some(grammar)
+            }
+            _ => None,
+        })
+        .collect();
+
+    assert_eq!(
+        grammars,
+        [r#"["yes","no","[unk]"]"#, r#"["start","stop"]"#, "[]"]
+    );
+}
+
+#[test]
+fn grammars_are_not_passed_to_static_graphs() {
+    let model_dir = FakeModelDir::with_static_graph();
+    let model = Model::new(model_dir.path()).unwrap();
+
+    assert!(matches!(
+        Recognizer::new_with_grammar(&model, 16000.0, &["yes"]),
+        Err(RecognizerError::GrammarNotSupported(_))
+    ));
+
+    let mut recognizer = Recognizer::new(&model, 16000.0).unwrap();
+    assert!(matches!(
+        recognizer.set_grammar(&Grammar::from(&["yes"])),
+        Err(RecognizerError::GrammarNotSupported(_))
+    ));
+
+    assert!(!vosk_mock::calls()
+        .iter()
+        .any(|call| matches!(call, Call::SetGrammar { .. })));
+}
+
+#[test]
+fn text_processor_converts_results() {
+    let model_dir = FakeModelDir::new();
+    model_dir.write("itn/tagger.fst", "");
+    model_dir.write("itn/verbalizer.fst", "");
+
+    let model = Model::new(model_dir.path()).unwrap();
+    let text_processor = TextProcessor::new(
+        model_dir.path().join("itn/tagger.fst"),
+        model_dir.path().join("itn/verbalizer.fst"),
+    )
+    .unwrap();
+    let mut recognizer = Recognizer::new(&model, 16000.0).unwrap();
+    recognizer.set_text_processor(&text_processor);
+
+    vosk_mock::script_itn("twenty one", "21");
+    vosk_mock::script_result(r#"{"text": "twenty one"}"#);
+    vosk_mock::script_final_result(r#"{"text": "twenty two"}"#);
+
+    assert_eq!(recognizer.result().single().unwrap().text, "21");
+    assert_eq!(
+        recognizer.final_result().single().unwrap().text,
+        "twenty two"
+    );
+    assert!(vosk_mock::calls().contains(&Call::Itn {
+        input: "twenty one".to_owned()
+    }));
+}
+
+#[test]
+fn results_outlive_the_recognizer() {
+    let model_dir = FakeModelDir::new();
+    let model = Model::new(model_dir.path()).unwrap();
+    let mut recognizer = Recognizer::new(&model, 16000.0).unwrap();
+
+    vosk_mock::script_result(r#"{"text": "first"}"#);
+    vosk_mock::script_partial_result(r#"{"partial": "sec"}"#);
+    vosk_mock::script_final_result(r#"{"text": "second"}"#);
+
+    // Every result is kept while the next ones are read
+    let first = recognizer.result();
+    let partial = recognizer.partial_result();
+    let second = recognizer.final_result();
+    drop(recognizer);
+
+    let (first, partial, second) = std::thread::spawn(move || (first, partial, second))
+        .join()
+        .unwrap();
+    assert_eq!(first.text(), Some("first"));
+    assert_eq!(partial.partial, "sec");
+    assert_eq!(second.text(), Some("second"));
+}
+
+#[test]
+fn failures_are_reported() {
+    let model_dir = FakeModelDir::new();
+    let model = Model::new(model_dir.path()).unwrap();
+
+    vosk_mock::set_fail_creation(true);
+    assert!(matches!(
+        Model::new(model_dir.path()),
+        Err(ModelError::LoadFailed(_))
+    ));
+    assert!(matches!(
+        Recognizer::new(&model, 16000.0),
+        Err(RecognizerError::CreationFailed)
+    ));
+    vosk_mock::set_fail_creation(false);
+
+    assert!(Recognizer::new(&model, 16000.0).is_ok());
+}
+
+#[test]
+fn missing_model_files_are_reported() {
+    let model_dir = FakeModelDir::with_files(&[("am/final.mdl", ""), ("README", "")]);
+
+    match Model::new(model_dir.path()) {
+        Err(ModelError::MissingFiles { path, missing }) => {
+            assert_eq!(path, model_dir.path());
+            assert_eq!(
+                missing,
+                [
+                    "conf/mfcc.conf",
+                    "a graph (graph/HCLG.fst, or graph/HCLr.fst and graph/Gr.fst)"
+                ]
+            );
+        }
+        result => panic!("expected a missing files error, got {:?}", result.err()),
+    }
+
+    let speaker_model_dir = FakeModelDir::with_files(&[("mfcc.conf", ""), ("mean.vec", "")]);
+    match vosk::SpeakerModel::new(speaker_model_dir.path()) {
+        Err(ModelError::MissingFiles { path, missing }) => {
+            assert_eq!(path, speaker_model_dir.path());
+            assert_eq!(missing, ["final.ext.raw", "transform.mat"]);
+        }
+        result => panic!("expected a missing files error, got {:?}", result.err()),
+    }
+
+    // Nothing is passed to Vosk
+    assert_eq!(vosk_mock::calls(), []);
+}
+
+#[test]
+fn graph_kind_is_detected_when_the_model_is_loaded() {
+    let model_dir = FakeModelDir::new();
+    let model = Model::new(model_dir.path()).unwrap();
+    assert_eq!(model.graph(), GraphKind::Lookahead);
+
+    let static_graph = FakeModelDir::with_static_graph();
+    assert_eq!(
+        Model::new(static_graph.path()).unwrap().graph(),
+        GraphKind::Static
+    );
+
+    // The directory is not read again when setting a grammar
+    std::fs::remove_dir_all(model_dir.path().join("graph")).unwrap();
+    let mut recognizer = Recognizer::new(&model, 16000.0).unwrap();
+    assert!(recognizer.set_grammar(&Grammar::from(&["yes"])).is_ok());
+    assert!(Recognizer::new_with_grammar(&model, 16000.0, &["yes"]).is_ok());
+}
+
+#[test]
+#[should_panic(expected = "scripted results cannot contain NUL bytes")]
+fn results_with_nul_bytes_cannot_be_scripted() {
+    vosk_mock::script_result("{\"text\": \"a\0b\"}");
+}
+
+#[test]
+fn every_object_is_freed() {
+    let model_dir = FakeModelDir::new();
+    let speaker_model_dir = FakeModelDir::speaker();
+
+    {
+        let model = Model::new(model_dir.path()).unwrap();
+        let speaker_model = vosk::SpeakerModel::new(speaker_model_dir.path()).unwrap();
+        let mut recognizer = Recognizer::new_with_speaker(&model, 16000.0, &speaker_model).unwrap();
+        recognizer.set_speaker_model(&speaker_model);
+
+        assert_eq!(vosk_mock::live_objects(), 3);
+    }
+
+    assert_eq!(vosk_mock::live_objects(), 0);
+    assert_eq!(vosk_mock::misuses(), Vec::<String>::new());
+}
+
+#[test]
+fn shared_models_outlive_their_handles_and_are_freed_once() {
+    let model_dir = FakeModelDir::new();
+    let speaker_model_dir = FakeModelDir::speaker();
+    let model = SharedModel::new(model_dir.path()).unwrap();
+    let speaker_model = SharedSpeakerModel::new(speaker_model_dir.path()).unwrap();
+    let other_handle = model.clone();
+
+    let mut recognizer: Recognizer<'static> =
+        Recognizer::new_with_speaker(model.clone(), 16000.0, speaker_model).unwrap();
+    // The handles this thread kept are not the last ones
+    drop(model);
+
+    vosk_mock::reset();
+    let calls = std::thread::spawn(move || {
+        recognizer.accept_waveform(&[1i16, 2, 3]).unwrap();
+        drop(recognizer);
+        (vosk_mock::take_calls(), vosk_mock::misuses())
+    })
+    .join()
+    .unwrap();
+
+    // The recognizer freed its speaker model, but the model still has a handle
+    assert_eq!(
+        calls,
+        (
+            vec![
+                Call::AcceptWaveformShort(vec![1, 2, 3]),
+                Call::RecognizerFree,
+                Call::SpeakerModelFree,
+            ],
+            Vec::new()
+        )
+    );
+    assert_eq!(vosk_mock::calls(), []);
+
+    drop(other_handle);
+    assert_eq!(vosk_mock::take_calls(), [Call::ModelFree]);
+    assert_eq!(vosk_mock::misuses(), Vec::<String>::new());
+}
+
+#[test]
+fn find_word_uses_the_vocabulary() {
+    let model_dir = FakeModelDir::new();
+    let model = Model::new(model_dir.path()).unwrap();
+
+    vosk_mock::set_vocabulary(["hello", "world"]);
+
+    assert_eq!(model.find_word("world"), Some(2));
+    assert_eq!(model.find_word("missing"), None);
+}
+
+#[test]
+fn speech_recognizer_results_outlive_the_next_call() {
+    let model_dir = FakeModelDir::new();
+    let model = Model::new(model_dir.path()).unwrap();
+    let mut recognizer = Recognizer::new(&model, 16000.0).unwrap();
+
+    vosk_mock::script_result(r#"{"text": "say \"hi\""}"#);
+    vosk_mock::script_result(r#"{"text": "bye"}"#);
+
+    let first = SpeechRecognizer::result(&mut recognizer);
+    let second = SpeechRecognizer::result(&mut recognizer);
+
+    assert_eq!(first.single().unwrap().text, r#"say "hi""#);
+    assert_eq!(second.single().unwrap().text, "bye");
+}
+
+#[test]
+fn nlsml_results_are_parsed_and_processed() {
+    let model_dir = FakeModelDir::new();
+    model_dir.write("itn/tagger.fst", "");
+    model_dir.write("itn/verbalizer.fst", "");
+
+    let model = Model::new(model_dir.path()).unwrap();
+    let text_processor = TextProcessor::new(
+        model_dir.path().join("itn/tagger.fst"),
+        model_dir.path().join("itn/verbalizer.fst"),
+    )
+    .unwrap();
+    let mut recognizer = Recognizer::new(&model, 16000.0).unwrap();
+    recognizer.set_nlsml(true);
+    recognizer.set_text_processor(&text_processor);
+
+    let xml = r#"<?xml version="1.0"?>
+<result grammar="default">
+<interpretation grammar="default" confidence="0.900000">
+<input mode="speech">twenty one</input>
+<instance>twenty one</instance>
+</interpretation>
+</result>
+"#;
+    vosk_mock::script_itn("twenty one", "21");
+    vosk_mock::script_final_result(xml);
+
+    let result = recognizer.final_result().nlsml().unwrap();
+    assert_eq!(result.text(), Some("21"));
+    assert_eq!(result.best().unwrap().instance, "twenty one");
+    assert_eq!(result.raw_xml, xml);
+    assert!(vosk_mock::calls().contains(&Call::SetNlsml(true)));
+}
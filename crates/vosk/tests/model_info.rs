@@ -0,0 +1,232 @@
+#![cfg(feature = "mock")]
+
+use std::path::Path;
+use vosk::{EndpointRule, GraphKind, ModelError, ModelInfo};
+use vosk_mock::FakeModelDir;
+
+#[test]
+fn sample_frequency_is_read_from_the_mfcc_conf() {
+    let model_dir = FakeModelDir::new();
+    model_dir.write(
+        "conf/mfcc.conf",
+        "--use-energy=false   # comment\n\n--sample-frequency=8000\n--num-ceps=40\n",
+    );
+
+    let info = ModelInfo::from_dir(model_dir.path()).unwrap();
+    assert_eq!(info.sample_frequency, Some(8000.0));
+
+    model_dir.write("conf/mfcc.conf", "--use-energy=false\n");
+    let info = ModelInfo::from_dir(model_dir.path()).unwrap();
+    assert_eq!(info.sample_frequency, None);
+}
+
+#[test]
+fn endpoint_rules_are_read_from_the_model_conf() {
+    let model_dir = FakeModelDir::new();
+    model_dir.write(
+        "conf/model.conf",
+        "--min-active=200\n\
+         --endpoint.silence-phones=1:2:3:4:5\n\
+         --endpoint.rule1.must-contain-nonsilence=false\n\
+         --endpoint.rule1.min-trailing-silence=5.0\n\
+         --endpoint.rule2.min-trailing-silence=0.5 --endpoint.rule2.max-relative-cost=2.0\n\
+         --endpoint.rule3.must-contain-nonsilence=1 --endpoint.rule3.min-utterance-length=20\n\
+         --endpoint.rule4.must-contain-nonsilence=F\n\
+         --endpoint.rule5.must-contain-nonsilence\n\
+         --endpoint.rule6.min-trailing-silence=1.0\n",
+    );
+
+    let endpoint = ModelInfo::from_dir(model_dir.path()).unwrap().endpoint;
+    assert_eq!(endpoint.silence_phones.as_deref(), Some("1:2:3:4:5"));
+    assert_eq!(
+        endpoint.rules,
+        [
+            EndpointRule {
+                must_contain_nonsilence: Some(false),
+                min_trailing_silence: Some(5.0),
+                ..EndpointRule::default()
+            },
+            EndpointRule {
+                min_trailing_silence: Some(0.5),
+                max_relative_cost: Some(2.0),
+                ..EndpointRule::default()
+            },
+            EndpointRule {
+                must_contain_nonsilence: Some(true),
+                min_utterance_length: Some(20.0),
+                ..EndpointRule::default()
+            },
+            EndpointRule {
+                must_contain_nonsilence: Some(false),
+                ..EndpointRule::default()
+            },
+            EndpointRule {
+                must_contain_nonsilence: Some(true),
+                ..EndpointRule::default()
+            },
+        ]
+    );
+}
+
+#[test]
+fn kaldi_booleans_are_accepted() {
+    let model_dir = FakeModelDir::new();
+
+    for (value, expected) in [
+        ("true", true),
+        ("T", true),
+        ("t", true),
+        ("1", true),
+        ("false", false),
+        ("f", false),
+        ("0", false),
+    ] {
+        model_dir.write(
+            "conf/model.conf",
+            &format!("--endpoint.rule1.must-contain-nonsilence={value}\n"),
+        );
+
+        let info = ModelInfo::from_dir(model_dir.path()).unwrap();
+        assert_eq!(
+            info.endpoint.rules[0].must_contain_nonsilence,
+            Some(expected),
+            "{value}"
+        );
+    }
+}
+
+#[test]
+fn endpoint_rules_are_empty_without_a_model_conf() {
+    let model_dir = FakeModelDir::new();
+
+    let endpoint = ModelInfo::from_dir(model_dir.path()).unwrap().endpoint;
+    assert_eq!(endpoint.silence_phones, None);
+    assert_eq!(endpoint.rules, [EndpointRule::default(); 5]);
+}
+
+#[test]
+fn graph_kind_is_detected_in_both_layouts() {
+    let lookahead = FakeModelDir::new();
+    assert_eq!(
+        ModelInfo::from_dir(lookahead.path()).unwrap().graph,
+        GraphKind::Lookahead
+    );
+
+    let static_graph = FakeModelDir::with_static_graph();
+    assert_eq!(
+        ModelInfo::from_dir(static_graph.path()).unwrap().graph,
+        GraphKind::Static
+    );
+
+    // Everything at the root of the directory
+    let legacy = FakeModelDir::with_files(&[
+        ("final.mdl", ""),
+        ("mfcc.conf", "--sample-frequency=8000\n"),
+        ("HCLG.fst", ""),
+        ("words.txt", "<eps> 0\nhello 1\n"),
+    ]);
+    let info = ModelInfo::from_dir(legacy.path()).unwrap();
+    assert_eq!(info.graph, GraphKind::Static);
+    assert_eq!(info.sample_frequency, Some(8000.0));
+    assert_eq!(info.vocabulary_size, Some(2));
+
+    let legacy_lookahead = FakeModelDir::with_files(&[
+        ("final.mdl", ""),
+        ("mfcc.conf", ""),
+        ("HCLr.fst", ""),
+        ("Gr.fst", ""),
+    ]);
+    assert_eq!(
+        ModelInfo::from_dir(legacy_lookahead.path()).unwrap().graph,
+        GraphKind::Lookahead
+    );
+}
+
+#[test]
+fn rescoring_data_is_detected() {
+    let model_dir = FakeModelDir::new();
+    let info = ModelInfo::from_dir(model_dir.path()).unwrap();
+    assert!(!info.has_rnnlm);
+    assert!(!info.has_rescore);
+
+    model_dir.write("rnnlm/final.raw", "");
+    model_dir.write("rescore/G.carpa", "");
+    let info = ModelInfo::from_dir(model_dir.path()).unwrap();
+    assert!(info.has_rnnlm);
+    assert!(info.has_rescore);
+
+    let model_dir = FakeModelDir::new();
+    model_dir.write("rescore/G.fst", "");
+    assert!(ModelInfo::from_dir(model_dir.path()).unwrap().has_rescore);
+}
+
+#[test]
+fn vocabulary_size_counts_the_non_empty_lines_of_words_txt() {
+    let model_dir = FakeModelDir::new();
+    assert_eq!(
+        ModelInfo::from_dir(model_dir.path())
+            .unwrap()
+            .vocabulary_size,
+        None
+    );
+
+    model_dir.write("graph/words.txt", "<eps> 0\nhello 1\n\nworld 2\n  \n");
+    assert_eq!(
+        ModelInfo::from_dir(model_dir.path())
+            .unwrap()
+            .vocabulary_size,
+        Some(3)
+    );
+}
+
+/// Asserts that `result` is an [`ModelError::InvalidFile`] error about `file`, at `line`.
+fn assert_invalid_file(result: Result<ModelInfo, ModelError>, file: &Path, line: usize) {
+    match result {
+        Err(ModelError::InvalidFile {
+            path,
+            line: error_line,
+            ..
+        }) => {
+            assert_eq!(path, file);
+            assert_eq!(error_line, line);
+        }
+        result => panic!("expected an invalid file error, got {result:?}"),
+    }
+}
+
+#[test]
+fn malformed_lines_are_reported_with_their_number() {
+    let model_dir = FakeModelDir::new();
+    let mfcc_conf = model_dir.path().join("conf/mfcc.conf");
+    let model_conf = model_dir.path().join("conf/model.conf");
+
+    model_dir.write(
+        "conf/mfcc.conf",
+        "# comment\n--use-energy=false\n--sample-frequency=fast\n",
+    );
+    assert_invalid_file(ModelInfo::from_dir(model_dir.path()), &mfcc_conf, 3);
+
+    model_dir.write(
+        "conf/mfcc.conf",
+        "--use-energy=false\nsample-frequency=8000\n",
+    );
+    assert_invalid_file(ModelInfo::from_dir(model_dir.path()), &mfcc_conf, 2);
+
+    model_dir.write("conf/mfcc.conf", "--sample-frequency=16000\n");
+    model_dir.write(
+        "conf/model.conf",
+        "--endpoint.rule1.min-trailing-silence=5\n--endpoint.rule1.must-contain-nonsilence=yes\n",
+    );
+    assert_invalid_file(ModelInfo::from_dir(model_dir.path()), &model_conf, 2);
+}
+
+#[test]
+fn missing_directories_are_reported() {
+    let model_dir = FakeModelDir::new();
+    let missing = model_dir.path().join("missing");
+
+    assert!(matches!(
+        ModelInfo::from_dir(&missing),
+        Err(ModelError::DirectoryNotFound(path)) if path == missing
+    ));
+}
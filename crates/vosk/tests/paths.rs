@@ -0,0 +1,71 @@
+#![cfg(feature = "mock")]
+
+use std::path::Path;
+use vosk::{models_dir, set_models_dir, Model, ModelError};
+use vosk_mock::{Call, FakeModelDir};
+
+#[test]
+fn relative_paths_are_resolved_against_the_models_dir() {
+    let model_dir = FakeModelDir::new();
+    let (parent, name) = (
+        model_dir.path().parent().unwrap(),
+        model_dir.path().file_name().unwrap(),
+    );
+
+    // The models directory is global, so it is only changed in this test
+    set_models_dir(Some(parent.to_owned()));
+    assert_eq!(models_dir().as_deref(), Some(parent));
+
+    vosk_mock::reset();
+    let model = Model::new(name).unwrap();
+    assert_eq!(model.path(), model_dir.path());
+    assert_eq!(
+        vosk_mock::calls(),
+        [Call::ModelNew {
+            path: model_dir.path().to_string_lossy().into_owned()
+        }]
+    );
+
+    // Absolute paths are not affected
+    assert!(Model::new(model_dir.path()).is_ok());
+
+    set_models_dir(None);
+    assert_eq!(models_dir(), None);
+    assert!(matches!(
+        Model::new(name),
+        Err(ModelError::DirectoryNotFound(path)) if path == Path::new(name)
+    ));
+}
+
+#[cfg(unix)]
+#[test]
+fn non_utf8_paths_are_passed_unchanged() {
+    use std::{ffi::OsStr, fs, os::unix::ffi::OsStrExt};
+
+    let fake_dir = FakeModelDir::new();
+    let model_path = fake_dir.path().join(OsStr::from_bytes(b"model-\xff"));
+    for file in ["am/final.mdl", "conf/mfcc.conf", "graph/HCLG.fst"] {
+        let file = model_path.join(file);
+        fs::create_dir_all(file.parent().unwrap()).unwrap();
+        fs::write(file, "").unwrap();
+    }
+    assert!(model_path.to_str().is_none());
+
+    vosk_mock::reset();
+    let model = Model::new(&model_path).unwrap();
+    assert_eq!(model.path(), model_path);
+    assert_eq!(
+        vosk_mock::calls(),
+        [Call::ModelNew {
+            path: model_path.to_string_lossy().into_owned()
+        }]
+    );
+}
+
+#[test]
+fn nul_bytes_in_paths_are_rejected() {
+    assert!(matches!(
+        Model::new("/path/with\0nul"),
+        Err(ModelError::NulInPath(_))
+    ));
+}
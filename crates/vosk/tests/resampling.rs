@@ -0,0 +1,245 @@
+#![cfg(feature = "mock")]
+
+use std::f32::consts::PI;
+
+use vosk::{Grammar, Model, Recognizer, RecognizerError};
+use vosk_mock::{Call, FakeModelDir};
+
+/// `seconds` of a sine wave at `frequency` Hz, sampled at `rate` Hz, with an amplitude of 0.5.
+fn sine(frequency: f32, rate: f32, seconds: f32) -> Vec<f32> {
+    let len = (rate * seconds) as usize;
+    (0..len)
+        .map(|index| 0.5 * (2.0 * PI * frequency * index as f32 / rate).sin())
+        .collect()
+}
+
+/// Audio Vosk received, scaled back to floats between -1 and 1.
+fn received(calls: Vec<Call>) -> Vec<f32> {
+    calls
+        .into_iter()
+        .flat_map(|call| match call {
+            Call::AcceptWaveformFloat(samples) => samples,
+            call => panic!("unexpected call {call:?}"),
+        })
+        .map(|sample| sample / 32768.0)
+        .collect()
+}
+
+/// Resamples `audio` from `input_rate` to the 16 kHz of the fake model, `chunk_size`
+/// samples at a time.
+fn resample(audio: &[f32], input_rate: f32, chunk_size: usize) -> Vec<f32> {
+    let model_dir = FakeModelDir::new();
+    let model = Model::new(model_dir.path()).unwrap();
+    let mut recognizer = Recognizer::with_input_rate(&model, input_rate).unwrap();
+
+    vosk_mock::reset();
+    for chunk in audio.chunks(chunk_size) {
+        recognizer.accept_samples(chunk).unwrap();
+    }
+    received(vosk_mock::take_calls())
+}
+
+/// Largest difference between `actual` and `expected`, ignoring the first and last
+/// `margin` samples, where the filter sees the edges of the signal.
+fn max_error(actual: &[f32], expected: &[f32], margin: usize) -> f32 {
+    actual[margin..actual.len() - margin]
+        .iter()
+        .zip(&expected[margin..])
+        .map(|(actual, expected)| (actual - expected).abs())
+        .fold(0.0, f32::max)
+}
+
+/// Largest amplitude in `audio`, ignoring the first and last `margin` samples.
+fn peak(audio: &[f32], margin: usize) -> f32 {
+    audio[margin..audio.len() - margin]
+        .iter()
+        .map(|sample| sample.abs())
+        .fold(0.0, f32::max)
+}
+
+#[test]
+fn target_rate_comes_from_the_model_config() {
+    let model_dir = FakeModelDir::new();
+    model_dir.write("conf/mfcc.conf", "--sample-frequency=8000\n");
+    let model = Model::new(model_dir.path()).unwrap();
+
+    vosk_mock::reset();
+    let _recognizer = Recognizer::with_input_rate(&model, 44100.0).unwrap();
+    assert_eq!(
+        vosk_mock::calls(),
+        [Call::RecognizerNew {
+            sample_rate: 8000.0
+        }]
+    );
+
+    model_dir.write("conf/mfcc.conf", "");
+    vosk_mock::reset();
+    let _recognizer = Recognizer::with_input_rate(&model, 44100.0).unwrap();
+    assert_eq!(
+        vosk_mock::calls(),
+        [Call::RecognizerNew {
+            sample_rate: Recognizer::DEFAULT_SAMPLE_RATE
+        }]
+    );
+}
+
+#[test]
+fn downsampling_keeps_the_signal() {
+    let output = resample(&sine(440.0, 48000.0, 1.0), 48000.0, 480);
+
+    assert!((15950..=16000).contains(&output.len()), "{}", output.len());
+    let error = max_error(&output, &sine(440.0, 16000.0, 1.0), 100);
+    assert!(error < 0.005, "error: {error}");
+}
+
+#[test]
+fn upsampling_keeps_the_signal() {
+    let output = resample(&sine(1000.0, 8000.0, 1.0), 8000.0, 160);
+
+    assert!((15950..=16000).contains(&output.len()), "{}", output.len());
+    let error = max_error(&output, &sine(1000.0, 16000.0, 1.0), 100);
+    assert!(error < 0.005, "error: {error}");
+}
+
+#[test]
+fn non_integer_ratios_keep_the_signal() {
+    let output = resample(&sine(300.0, 44100.0, 1.0), 44100.0, 441);
+
+    assert!((15950..=16000).contains(&output.len()), "{}", output.len());
+    let error = max_error(&output, &sine(300.0, 16000.0, 1.0), 100);
+    assert!(error < 0.005, "error: {error}");
+}
+
+#[test]
+fn frequencies_above_the_target_nyquist_frequency_are_removed() {
+    // 12 kHz would alias to 4 kHz at 16 kHz
+    let output = resample(&sine(12000.0, 48000.0, 0.5), 48000.0, 480);
+
+    let peak = peak(&output, 100);
+    assert!(peak < 0.01, "peak: {peak}");
+}
+
+#[test]
+fn chunk_boundaries_do_not_change_the_output() {
+    let input = sine(440.0, 44100.0, 0.2);
+    let whole = resample(&input, 44100.0, input.len());
+
+    for chunk_size in [1, 7, 100, 1234] {
+        assert_eq!(resample(&input, 44100.0, chunk_size), whole, "{chunk_size}");
+    }
+}
+
+#[test]
+fn final_result_flushes_the_pending_audio() {
+    let model_dir = FakeModelDir::new();
+    let model = Model::new(model_dir.path()).unwrap();
+    let mut recognizer = Recognizer::with_input_rate(&model, 44100.0).unwrap();
+    let input = sine(300.0, 44100.0, 0.5);
+
+    vosk_mock::reset();
+    for chunk in input.chunks(441) {
+        recognizer.accept_samples(chunk).unwrap();
+    }
+    let before_final = received(vosk_mock::take_calls()).len();
+    let _ = recognizer.final_result();
+
+    let mut calls = vosk_mock::take_calls();
+    assert_eq!(calls.pop(), Some(Call::FinalResult));
+    let tail = received(calls);
+    assert!(!tail.is_empty());
+    assert_eq!(before_final + tail.len(), 8000);
+
+    // The sine ends at a zero crossing, so the silence after it does not change the tail
+    let expected = sine(300.0, 16000.0, 0.5);
+    let error = max_error(&tail, &expected[before_final..], 0);
+    assert!(error < 0.005, "error: {error}");
+
+    // The next utterance starts from silence
+    for chunk in input.chunks(441) {
+        recognizer.accept_samples(chunk).unwrap();
+    }
+    assert_eq!(
+        received(vosk_mock::take_calls()),
+        resample(&input, 44100.0, 441)
+    );
+}
+
+#[test]
+fn reset_discards_the_pending_audio() {
+    let model_dir = FakeModelDir::new();
+    let model = Model::new(model_dir.path()).unwrap();
+    let mut recognizer = Recognizer::with_input_rate(&model, 48000.0).unwrap();
+    let input = sine(440.0, 48000.0, 0.1);
+
+    vosk_mock::reset();
+    recognizer.accept_samples(&input).unwrap();
+    let first = received(vosk_mock::take_calls());
+
+    recognizer.accept_samples(&[0.9f32; 100]).unwrap();
+    recognizer.reset();
+    vosk_mock::reset();
+    recognizer.accept_samples(&input).unwrap();
+
+    assert_eq!(
+        received(vosk_mock::take_calls()),
+        first,
+        "the audio before the reset leaked into the output"
+    );
+}
+
+#[test]
+fn setting_a_grammar_discards_the_pending_audio() {
+    let model_dir = FakeModelDir::new();
+    let model = Model::new(model_dir.path()).unwrap();
+    let mut recognizer = Recognizer::with_input_rate(&model, 48000.0).unwrap();
+    let input = sine(440.0, 48000.0, 0.1);
+
+    vosk_mock::reset();
+    recognizer.accept_samples(&input).unwrap();
+    let first = received(vosk_mock::take_calls());
+
+    recognizer.accept_samples(&[0.9f32; 100]).unwrap();
+    recognizer
+        .set_grammar(&Grammar::new().phrase("hello"))
+        .unwrap();
+    vosk_mock::reset();
+    recognizer.accept_samples(&input).unwrap();
+
+    assert_eq!(
+        received(vosk_mock::take_calls()),
+        first,
+        "the audio before the grammar was set leaked into the output"
+    );
+}
+
+#[test]
+fn matching_rates_are_not_resampled() {
+    let model_dir = FakeModelDir::new();
+    let model = Model::new(model_dir.path()).unwrap();
+    let mut recognizer = Recognizer::with_input_rate(&model, 16000.0).unwrap();
+
+    vosk_mock::reset();
+    recognizer.accept_waveform(&[1i16, 2, 3]).unwrap();
+    assert_eq!(
+        vosk_mock::calls(),
+        [Call::AcceptWaveformShort(vec![1, 2, 3])]
+    );
+}
+
+#[test]
+fn invalid_rates_are_rejected() {
+    let model_dir = FakeModelDir::new();
+    let model = Model::new(model_dir.path()).unwrap();
+    let mut recognizer = Recognizer::new(&model, 16000.0).unwrap();
+
+    for rate in [0.0, -8000.0, f32::NAN, f32::INFINITY] {
+        assert!(matches!(
+            recognizer.set_input_rate(rate),
+            Err(RecognizerError::InvalidSampleRate(_))
+        ));
+    }
+    assert!(matches!(
+        Recognizer::with_input_rate(&model, 0.0),
+        Err(RecognizerError::InvalidSampleRate(_))
+    ));
+}
@@ -0,0 +1,140 @@
+use serde::Deserialize;
+use std::borrow::Cow;
+use vosk::{
+    Alternative, CompleteResult, CompleteResultMultiple, CompleteResultSingle, PartialResult, Word,
+    WordInAlternative,
+};
+
+const SINGLE: &str = r#"{
+    "result": [
+        {"conf": 1.0, "end": 0.9, "start": 0.5, "word": "hello"},
+        {"conf": 0.5, "end": 1.5, "start": 1.0, "word": "world"}
+    ],
+    "text": "hello world"
+}"#;
+
+const MULTIPLE: &str = r#"{
+    "alternatives": [
+        {"confidence": 200.5, "result": [{"end": 0.9, "start": 0.5, "word": "hello"}], "text": "hello"},
+        {"confidence": 100.0, "result": [{"end": 0.9, "start": 0.5, "word": "yellow"}], "text": "yellow"}
+    ]
+}"#;
+
+fn expected_single() -> CompleteResultSingle<'static> {
+    CompleteResultSingle {
+        speaker_info: None,
+        result: vec![
+            Word {
+                conf: 1.0,
+                start: 0.5,
+                end: 0.9,
+                word: Cow::Borrowed("hello"),
+            },
+            Word {
+                conf: 0.5,
+                start: 1.0,
+                end: 1.5,
+                word: Cow::Borrowed("world"),
+            },
+        ],
+        text: Cow::Borrowed("hello world"),
+    }
+}
+
+#[test]
+fn results_deserialize_from_values() {
+    let value: serde_json::Value = serde_json::from_str(SINGLE).unwrap();
+    let result: CompleteResult<'static> = serde_json::from_value(value).unwrap();
+
+    assert_eq!(result, CompleteResult::Single(expected_single()));
+}
+
+#[test]
+fn results_deserialize_from_readers() {
+    let result: CompleteResult<'static> = serde_json::from_reader(MULTIPLE.as_bytes()).unwrap();
+
+    let word = |word| WordInAlternative {
+        start: 0.5,
+        end: 0.9,
+        word: Cow::Borrowed(word),
+    };
+    assert_eq!(
+        result.multiple().unwrap(),
+        CompleteResultMultiple {
+            speaker_info: None,
+            alternatives: vec![
+                Alternative {
+                    confidence: 200.5,
+                    result: vec![word("hello")],
+                    text: Cow::Borrowed("hello"),
+                },
+                Alternative {
+                    confidence: 100.0,
+                    result: vec![word("yellow")],
+                    text: Cow::Borrowed("yellow"),
+                },
+            ],
+        }
+    );
+}
+
+#[test]
+fn results_can_be_part_of_owned_types() {
+    #[derive(Deserialize)]
+    struct Message {
+        channel: u8,
+        result: CompleteResult<'static>,
+        partial: PartialResult<'static>,
+    }
+
+    let json = format!(r#"{{"channel": 1, "result": {SINGLE}, "partial": {{"partial": "hel"}}}}"#);
+    let message: Message = serde_json::from_reader(json.as_bytes()).unwrap();
+
+    assert_eq!(message.channel, 1);
+    assert_eq!(message.result, CompleteResult::Single(expected_single()));
+    assert_eq!(message.partial.partial, "hel");
+}
+
+#[test]
+fn into_owned_keeps_the_contents() {
+    let text = String::from("hello world");
+    let borrowed = CompleteResultSingle {
+        text: Cow::Borrowed(&text),
+        ..expected_single()
+    };
+
+    let owned = CompleteResult::Single(borrowed.clone()).into_owned();
+    drop(text);
+
+    assert_eq!(owned, CompleteResult::Single(expected_single()));
+    assert!(matches!(
+        owned.single().unwrap().text,
+        Cow::Owned(text) if text == "hello world"
+    ));
+}
+
+#[test]
+fn owned_results_can_be_sent_to_other_threads() {
+    let result: CompleteResult = serde_json::from_str(SINGLE).unwrap();
+    let result = result.into_owned();
+
+    let text = std::thread::spawn(move || result.single().unwrap().text)
+        .join()
+        .unwrap();
+    assert_eq!(text, "hello world");
+}
+
+#[test]
+fn results_round_trip_through_serialization() {
+    let partial = PartialResult {
+        partial: Cow::Borrowed("hello wor"),
+        partial_result: expected_single().result,
+    };
+
+    let json = serde_json::to_string(&partial).unwrap();
+    let decoded: PartialResult<'static> = serde_json::from_str::<PartialResult>(&json)
+        .unwrap()
+        .into_owned();
+
+    assert_eq!(decoded, partial);
+}
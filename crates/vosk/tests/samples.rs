@@ -0,0 +1,115 @@
+#![cfg(feature = "mock")]
+
+use vosk::{AcceptWaveformError, AudioSample, Model, Recognizer};
+use vosk_mock::{Call, FakeModelDir};
+
+/// Accepts `data` with a new recognizer and returns the call Vosk received.
+fn accepted<S: AudioSample>(data: &[S]) -> Call {
+    let model_dir = FakeModelDir::new();
+    let model = Model::new(model_dir.path()).unwrap();
+    let mut recognizer = Recognizer::new(&model, 16000.0).unwrap();
+
+    vosk_mock::reset();
+    recognizer.accept_samples(data).unwrap();
+    vosk_mock::take_calls().remove(0)
+}
+
+#[test]
+fn i16_is_passed_unchanged() {
+    assert_eq!(
+        accepted(&[0i16, 1, -1, i16::MAX, i16::MIN]),
+        Call::AcceptWaveformShort(vec![0, 1, -1, i16::MAX, i16::MIN])
+    );
+}
+
+#[test]
+fn untyped_literals_are_16_bit_with_accept_waveform() {
+    let model_dir = FakeModelDir::new();
+    let model = Model::new(model_dir.path()).unwrap();
+    let mut recognizer = Recognizer::new(&model, 16000.0).unwrap();
+
+    vosk_mock::reset();
+    recognizer.accept_waveform(&[1000, -2000]).unwrap();
+    assert_eq!(
+        vosk_mock::take_calls(),
+        [Call::AcceptWaveformShort(vec![1000, -2000])]
+    );
+}
+
+#[test]
+fn floats_are_scaled_to_16_bit() {
+    assert_eq!(
+        accepted(&[0.0f32, 0.5, -1.0]),
+        Call::AcceptWaveformFloat(vec![0.0, 16384.0, -32768.0])
+    );
+    assert_eq!(
+        accepted(&[0.0f64, -0.25, 1.0]),
+        Call::AcceptWaveformFloat(vec![0.0, -8192.0, 32768.0])
+    );
+}
+
+#[test]
+fn wider_and_unsigned_integers_are_converted_to_16_bit() {
+    assert_eq!(
+        accepted(&[0i32, 65536, -65536, i32::MAX, i32::MIN]),
+        Call::AcceptWaveformShort(vec![0, 1, -1, i16::MAX, i16::MIN])
+    );
+    assert_eq!(
+        accepted(&[32768u16, 32769, 0, u16::MAX]),
+        Call::AcceptWaveformShort(vec![0, 1, i16::MIN, i16::MAX])
+    );
+}
+
+#[test]
+fn bytes_are_read_as_little_endian() {
+    // The samples 1 and 32767, which Vosk reads in the native byte order
+    let expected = if cfg!(target_endian = "little") {
+        Call::AcceptWaveform(vec![0x01, 0x00, 0xff, 0x7f])
+    } else {
+        Call::AcceptWaveformShort(vec![1, i16::MAX])
+    };
+
+    assert_eq!(accepted(&[0x01u8, 0x00, 0xff, 0x7f]), expected);
+    assert_eq!(accepted(&[1i8, 0, -1, 127]), expected);
+}
+
+#[test]
+fn odd_byte_counts_are_rejected() {
+    let model_dir = FakeModelDir::new();
+    let model = Model::new(model_dir.path()).unwrap();
+    let mut recognizer = Recognizer::new(&model, 16000.0).unwrap();
+
+    vosk_mock::reset();
+    assert!(matches!(
+        recognizer.accept_samples(&[0u8; 3]),
+        Err(AcceptWaveformError::OddByteCount(3))
+    ));
+    assert!(matches!(
+        recognizer.accept_samples(&[0i8; 1]),
+        Err(AcceptWaveformError::OddByteCount(1))
+    ));
+    assert_eq!(vosk_mock::calls(), []);
+}
+
+#[test]
+fn converted_chunks_do_not_leak_into_each_other() {
+    let model_dir = FakeModelDir::new();
+    let model = Model::new(model_dir.path()).unwrap();
+    let mut recognizer = Recognizer::new(&model, 16000.0).unwrap();
+
+    vosk_mock::reset();
+    recognizer.accept_samples(&[0.5f32; 4]).unwrap();
+    recognizer.accept_samples(&[0.25f32; 2]).unwrap();
+    recognizer.accept_samples(&[65536i32; 3]).unwrap();
+    recognizer.accept_samples(&[131072i32]).unwrap();
+
+    assert_eq!(
+        vosk_mock::calls(),
+        [
+            Call::AcceptWaveformFloat(vec![16384.0; 4]),
+            Call::AcceptWaveformFloat(vec![8192.0; 2]),
+            Call::AcceptWaveformShort(vec![1; 3]),
+            Call::AcceptWaveformShort(vec![2]),
+        ]
+    );
+}
@@ -0,0 +1,135 @@
+use vosk::{CompleteResult, DecodingState, ScriptedRecognizer, SpeechRecognizer};
+
+/// Feeds `audio` in chunks and collects the partial and complete texts, like an application would.
+fn run(recognizer: &mut impl SpeechRecognizer, audio: &[i16]) -> Vec<String> {
+    let mut texts = Vec::new();
+
+    for chunk in audio.chunks(4) {
+        match recognizer.accept_waveform(chunk).unwrap() {
+            DecodingState::Finalized => texts.push(text(recognizer.result())),
+            DecodingState::Running => {
+                texts.push(format!("partial: {}", recognizer.partial_result().partial));
+            }
+            DecodingState::Failed => texts.push("failed".to_owned()),
+        }
+    }
+
+    texts.push(text(recognizer.final_result()));
+    texts
+}
+
+fn text(result: CompleteResult) -> String {
+    result.single().unwrap().text.into_owned()
+}
+
+#[test]
+fn scripted_states_and_results_are_returned_in_order() {
+    let mut recognizer = ScriptedRecognizer::new();
+    recognizer.push_partial_result_text("hel");
+    recognizer.push_decoding_state(DecodingState::Running);
+    recognizer.push_decoding_state(DecodingState::Finalized);
+    recognizer.push_decoding_state(DecodingState::Failed);
+    recognizer.push_result_text("hello");
+    recognizer.push_final_result_text("world");
+
+    let audio: Vec<i16> = (0..14).collect();
+    assert_eq!(
+        run(&mut recognizer, &audio),
+        ["partial: hel", "hello", "failed", "partial: ", "world"]
+    );
+    assert_eq!(recognizer.accepted_samples(), audio);
+}
+
+#[test]
+fn empty_results_once_the_script_is_exhausted() {
+    let mut recognizer = ScriptedRecognizer::new();
+
+    assert_eq!(
+        recognizer.accept_waveform(&[1, 2]).unwrap(),
+        DecodingState::Running
+    );
+    assert_eq!(text(recognizer.result()), "");
+    assert_eq!(recognizer.partial_result().partial, "");
+    assert_eq!(text(recognizer.final_result()), "");
+}
+
+#[test]
+fn float_samples_are_converted_to_16_bit() {
+    let mut recognizer = ScriptedRecognizer::new();
+    recognizer.push_decoding_state(DecodingState::Finalized);
+
+    assert_eq!(
+        recognizer
+            .accept_waveform_f32(&[0.0, 0.5, -0.5, -1.0, 1.0, 2.0])
+            .unwrap(),
+        DecodingState::Finalized
+    );
+    assert_eq!(
+        recognizer.accepted_samples(),
+        [0, 16384, -16384, -32768, 32767, 32767]
+    );
+}
+
+#[test]
+fn long_float_audio_is_converted_in_chunks() {
+    let audio: Vec<f32> = (0..10_000).map(|i| (i % 100) as f32 / 100.0).collect();
+    let expected: Vec<i16> = audio
+        .iter()
+        .map(|&sample| (sample * 32768.0) as i16)
+        .collect();
+
+    // The audio is passed in three calls, the second of which ends an utterance
+    let mut recognizer = ScriptedRecognizer::new();
+    recognizer.push_decoding_state(DecodingState::Running);
+    recognizer.push_decoding_state(DecodingState::Finalized);
+    recognizer.push_decoding_state(DecodingState::Running);
+
+    assert_eq!(
+        recognizer.accept_waveform_f32(&audio).unwrap(),
+        DecodingState::Finalized
+    );
+    assert_eq!(recognizer.accepted_samples(), expected);
+
+    // The rest of the audio is not passed after a failure
+    let mut recognizer = ScriptedRecognizer::new();
+    recognizer.push_decoding_state(DecodingState::Failed);
+
+    assert_eq!(
+        recognizer.accept_waveform_f32(&audio).unwrap(),
+        DecodingState::Failed
+    );
+    assert_eq!(recognizer.accepted_samples(), &expected[..4096]);
+}
+
+#[test]
+fn results_are_owned() {
+    let mut recognizer = ScriptedRecognizer::new();
+    recognizer.push_result_text("first");
+    recognizer.push_result_text("second");
+
+    let first = recognizer.result();
+    let second = recognizer.result();
+
+    assert_eq!(text(first), "first");
+    assert_eq!(text(second), "second");
+}
+
+#[test]
+fn recognizers_can_be_boxed_and_borrowed() {
+    let mut recognizer = ScriptedRecognizer::new();
+    recognizer.push_final_result_text("borrowed");
+    assert_eq!(run(&mut &mut recognizer, &[]), ["borrowed"]);
+
+    let mut boxed: Box<dyn SpeechRecognizer> = Box::new(recognizer);
+    boxed.reset();
+    assert_eq!(run(&mut boxed, &[]), [""]);
+}
+
+#[test]
+fn resets_are_counted() {
+    let mut recognizer = ScriptedRecognizer::new();
+    recognizer.reset();
+    recognizer.reset();
+
+    assert_eq!(recognizer.resets(), 2);
+}
@@ -0,0 +1,213 @@
+use vosk::{
+    AcceptWaveformError, DecodingState, Event, ScriptedRecognizer, SpeechRecognizer, Transcriber,
+    TranscriberError,
+};
+
+/// Summary of an event, to compare them.
+fn describe(event: &Event) -> String {
+    match event {
+        Event::Partial(partial) => format!("partial {}", partial.partial),
+        Event::Final(result) => format!("final {}", result.text().unwrap()),
+        Event::Failed(TranscriberError::RecognitionFailed) => "failed".to_owned(),
+        Event::Failed(error) => format!("failed: {error}"),
+        Event::EndOfStream(result) => format!("end {}", result.text().unwrap()),
+    }
+}
+
+#[test]
+fn events_follow_the_decoding_states() {
+    let mut recognizer = ScriptedRecognizer::new();
+    recognizer.push_decoding_state(DecodingState::Running);
+    recognizer.push_decoding_state(DecodingState::Finalized);
+    recognizer.push_decoding_state(DecodingState::Failed);
+    recognizer.push_decoding_state(DecodingState::Running);
+    recognizer.push_partial_result_text("hello");
+    recognizer.push_partial_result_text("how");
+    recognizer.push_result_text("hello world");
+    recognizer.push_final_result_text("how are you");
+
+    let events: Vec<_> = Transcriber::new(&mut recognizer, vec![0i16; 400])
+        .with_chunk_size(100)
+        .map(|event| describe(&event))
+        .collect();
+
+    assert_eq!(
+        events,
+        [
+            "partial hello",
+            "final hello world",
+            "failed",
+            "partial how",
+            "end how are you"
+        ]
+    );
+}
+
+#[test]
+fn audio_is_passed_in_chunks() {
+    let mut recognizer = ScriptedRecognizer::new();
+    let audio: Vec<i16> = (0..250).collect();
+
+    let events = Transcriber::new(&mut recognizer, audio.iter().copied())
+        .with_chunk_size(100)
+        .count();
+
+    // Two full chunks, the remaining 50 samples, and the end of the stream
+    assert_eq!(events, 4);
+    assert_eq!(recognizer.accepted_samples(), audio);
+}
+
+#[test]
+fn empty_audio_only_ends_the_stream() {
+    let mut recognizer = ScriptedRecognizer::new();
+    recognizer.push_final_result_text("");
+
+    let events: Vec<_> = Transcriber::new(&mut recognizer, Vec::new())
+        .map(|event| describe(&event))
+        .collect();
+
+    assert_eq!(events, ["end "]);
+    assert!(recognizer.accepted_samples().is_empty());
+}
+
+#[test]
+fn audio_is_read_lazily() {
+    let mut read = 0;
+    let audio = std::iter::repeat(0i16).inspect(|_| read += 1);
+
+    let mut transcriber = Transcriber::new(ScriptedRecognizer::new(), audio).with_chunk_size(10);
+    transcriber.next();
+    transcriber.next();
+    drop(transcriber);
+
+    assert_eq!(read, 20);
+}
+
+#[test]
+fn nothing_follows_the_end_of_the_stream() {
+    let mut transcriber = Transcriber::new(ScriptedRecognizer::new(), vec![0i16; 10]);
+
+    assert!(matches!(transcriber.next(), Some(Event::Partial(_))));
+    assert!(matches!(transcriber.next(), Some(Event::EndOfStream(_))));
+    assert!(transcriber.next().is_none());
+    assert!(transcriber.next().is_none());
+}
+
+#[test]
+fn rejected_chunks_are_reported() {
+    /// A recognizer that only takes chunks of an even length.
+    struct EvenRecognizer(ScriptedRecognizer);
+
+    impl SpeechRecognizer for EvenRecognizer {
+        fn accept_waveform(&mut self, data: &[i16]) -> Result<DecodingState, AcceptWaveformError> {
+            if data.len() % 2 == 1 {
+                return Err(AcceptWaveformError::OddByteCount(data.len()));
+            }
+            self.0.accept_waveform(data)
+        }
+
+        fn result(&mut self) -> vosk::CompleteResult<'static> {
+            self.0.result()
+        }
+
+        fn partial_result(&mut self) -> vosk::PartialResult<'static> {
+            self.0.partial_result()
+        }
+
+        fn final_result(&mut self) -> vosk::CompleteResult<'static> {
+            self.0.final_result()
+        }
+
+        fn reset(&mut self) {
+            self.0.reset();
+        }
+    }
+
+    let events: Vec<_> = Transcriber::new(EvenRecognizer(ScriptedRecognizer::new()), vec![0; 5])
+        .with_chunk_size(4)
+        .collect();
+
+    assert!(matches!(
+        events[..],
+        [
+            Event::Partial(_),
+            Event::Failed(TranscriberError::AcceptWaveform(
+                AcceptWaveformError::OddByteCount(1)
+            )),
+            Event::EndOfStream(_)
+        ]
+    ));
+}
+
+#[test]
+#[should_panic = "the chunk size must not be 0"]
+fn chunks_cannot_be_empty() {
+    let _ = Transcriber::new(ScriptedRecognizer::new(), Vec::new()).with_chunk_size(0);
+}
+
+#[cfg(feature = "mock")]
+mod recognizer {
+    use vosk::{Event, Model, Recognizer, ResultError, Transcriber, TranscriberError};
+    use vosk_mock::{Call, DecodingState, FakeModelDir};
+
+    #[test]
+    fn a_recognizer_can_be_used_after_the_transcription() {
+        let model_dir = FakeModelDir::new();
+        let model = Model::new(model_dir.path()).unwrap();
+        let mut recognizer = Recognizer::new(&model, 16000.0).unwrap();
+
+        vosk_mock::reset();
+        vosk_mock::script_decoding_state(DecodingState::Finalized);
+        vosk_mock::script_result(r#"{"text": "hello"}"#);
+        vosk_mock::script_final_result(r#"{"text": "world"}"#);
+
+        let events: Vec<_> = Transcriber::new(&mut recognizer, vec![1i16; 6])
+            .with_chunk_size(4)
+            .collect();
+
+        assert!(matches!(
+            &events[..],
+            [Event::Final(hello), Event::Partial(_), Event::EndOfStream(world)]
+                if hello.text() == Some("hello") && world.text() == Some("world")
+        ));
+        assert_eq!(
+            vosk_mock::take_calls(),
+            [
+                Call::AcceptWaveformShort(vec![1; 4]),
+                Call::Result,
+                Call::AcceptWaveformShort(vec![1; 2]),
+                Call::PartialResult,
+                Call::FinalResult,
+            ]
+        );
+
+        recognizer.reset();
+        assert_eq!(vosk_mock::calls(), [Call::Reset]);
+    }
+
+    #[test]
+    fn results_that_cannot_be_parsed_are_reported() {
+        let model_dir = FakeModelDir::new();
+        let model = Model::new(model_dir.path()).unwrap();
+        let recognizer = Recognizer::new(&model, 16000.0).unwrap();
+
+        vosk_mock::reset();
+        vosk_mock::script_decoding_state(DecodingState::Finalized);
+        vosk_mock::script_result("not json");
+        vosk_mock::script_partial_result("{");
+        vosk_mock::script_final_result(r#"{"text": 1}"#);
+
+        let events: Vec<_> = Transcriber::new(recognizer, vec![1i16; 8])
+            .with_chunk_size(4)
+            .collect();
+
+        assert!(matches!(
+            &events[..],
+            [
+                Event::Failed(TranscriberError::Result(ResultError::InvalidJson { raw: result, .. })),
+                Event::Failed(TranscriberError::Result(ResultError::InvalidJson { raw: partial, .. })),
+                Event::Failed(TranscriberError::Result(ResultError::InvalidJson { raw: final_result, .. })),
+            ] if result == "not json" && partial == "{" && final_result == r#"{"text": 1}"#
+        ));
+    }
+}
@@ -0,0 +1,135 @@
+#![cfg(feature = "mock")]
+
+use vosk::{ModelError, Vocabulary};
+use vosk_mock::FakeModelDir;
+
+const WORDS: &str = "<eps> 0\n#0 8\nhello 1\nhelmet 2\nhelp 3\nwork 4\nword 5\nworld 6\n";
+
+fn vocabulary() -> Vocabulary {
+    let model_dir = FakeModelDir::new();
+    model_dir.write("graph/words.txt", WORDS);
+    Vocabulary::from_dir(model_dir.path()).unwrap()
+}
+
+#[test]
+fn words_are_read_with_their_ids() {
+    let vocabulary = vocabulary();
+
+    assert_eq!(vocabulary.len(), 8);
+    assert_eq!(vocabulary.get("help"), Some(3));
+    assert_eq!(vocabulary.get("helping"), None);
+    assert!(vocabulary.contains("<eps>"));
+
+    // Everything at the root of the directory
+    let legacy = FakeModelDir::with_files(&[
+        ("final.mdl", ""),
+        ("mfcc.conf", ""),
+        ("HCLG.fst", ""),
+        ("words.txt", "<eps> 0\n\nhello 1\n"),
+    ]);
+    let vocabulary = Vocabulary::from_dir(legacy.path()).unwrap();
+    assert_eq!(
+        vocabulary.iter().collect::<Vec<_>>(),
+        [("<eps>", 0), ("hello", 1)]
+    );
+}
+
+#[test]
+fn words_are_found_by_prefix() {
+    let vocabulary = vocabulary();
+
+    assert_eq!(
+        vocabulary.with_prefix("hel").collect::<Vec<_>>(),
+        [("hello", 1), ("helmet", 2), ("help", 3)]
+    );
+    assert_eq!(
+        vocabulary.with_prefix("world").collect::<Vec<_>>(),
+        [("world", 6)]
+    );
+    assert_eq!(vocabulary.with_prefix("x").count(), 0);
+    assert_eq!(vocabulary.with_prefix("").count(), vocabulary.len());
+}
+
+#[test]
+fn suggestions_are_the_closest_words() {
+    let vocabulary = vocabulary();
+
+    assert_eq!(vocabulary.suggestions("helo", 1), ["hello", "help"]);
+    assert_eq!(
+        vocabulary.suggestions("helo", 3),
+        ["hello", "help", "helmet"]
+    );
+    assert_eq!(vocabulary.suggestions("wrod", 2), ["word", "world"]);
+    assert_eq!(vocabulary.suggestions("wrod", 3), ["word", "world", "work"]);
+    assert_eq!(vocabulary.suggestions("help", 0), ["help"]);
+
+    // Special symbols are never suggested
+    assert!(vocabulary.suggestions("<ep>", 1).is_empty());
+    assert!(vocabulary.suggestions("#1", 1).is_empty());
+}
+
+#[test]
+fn suggestions_stop_at_the_maximum_distance() {
+    let vocabulary = vocabulary();
+
+    // Too long or too short to be within the distance
+    assert!(vocabulary.suggestions("helloooo", 2).is_empty());
+    assert!(vocabulary.suggestions("he", 1).is_empty());
+
+    // Same length, but every row of the distance matrix goes over the bound
+    assert!(vocabulary.suggestions("zzzz", 1).is_empty());
+    assert!(vocabulary.suggestions("hzzz", 2).is_empty());
+    assert_eq!(vocabulary.suggestions("hzzz", 3), ["help"]);
+}
+
+#[test]
+fn missing_words_are_listed() {
+    let vocabulary = vocabulary();
+
+    assert!(vocabulary.contains_all(&["hello", "world"]));
+    assert!(vocabulary.contains_all(&[]));
+    assert!(!vocabulary.contains_all(&["hello", "there", "world"]));
+
+    assert_eq!(
+        vocabulary.missing(&["turn", "hello", "the", "world", "turn"]),
+        ["turn", "the", "turn"]
+    );
+    assert!(vocabulary.missing(&["hello", "world"]).is_empty());
+}
+
+#[test]
+fn malformed_words_are_reported_with_their_line() {
+    let model_dir = FakeModelDir::new();
+    let words_path = model_dir.path().join("graph/words.txt");
+
+    for (words, expected_line) in [
+        ("<eps> 0\nhello\n", 2),
+        ("<eps> 0\n\nhello one\n", 3),
+        ("hello 1 2\n", 1),
+    ] {
+        model_dir.write("graph/words.txt", words);
+
+        match Vocabulary::from_dir(model_dir.path()) {
+            Err(ModelError::InvalidFile { path, line, .. }) => {
+                assert_eq!(path, words_path);
+                assert_eq!(line, expected_line, "{words:?}");
+            }
+            result => panic!("expected an invalid file error, got {result:?}"),
+        }
+    }
+}
+
+#[test]
+fn missing_directories_and_files_are_reported() {
+    let model_dir = FakeModelDir::new();
+    let missing = model_dir.path().join("missing");
+
+    assert!(matches!(
+        Vocabulary::from_dir(&missing),
+        Err(ModelError::DirectoryNotFound(path)) if path == missing
+    ));
+    assert!(matches!(
+        Vocabulary::from_dir(model_dir.path()),
+        Err(ModelError::MissingFiles { missing, .. }) if missing == ["graph/words.txt"]
+    ));
+}
@@ -25,10 +25,10 @@ pkgs.mkShell {
     ];
     nativeBuildInputs = with pkgs; [ pkg-config ];
 
-    RUSTFLAGS = "-L${voskLib}";
+    # Found by the build script of vosk-sys, which also adds it to the rpath of the examples
+    VOSK_LIB_DIR = voskLib;
     LD_LIBRARY_PATH = lib.makeLibraryPath [
         pkgs.stdenv.cc.cc
-        voskLib
     ];
 
     # Run the examples like "cargo run --example <example> $MODEL $SPEAKER_MODEL" etc.